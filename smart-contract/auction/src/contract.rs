@@ -2,15 +2,29 @@
 
 mod state;
 
-use self::state::{AuctionData, AuctionState};
+use self::state::{
+    pool_bidder, AssetQuantity, AuctionData, AuctionState, GiftedQuantity, PendingBid,
+    PoolContribution, PoolData, SCHEMA_VERSION,
+};
 use auction::{AuctionAbi, AuctionOperation, AuctionParameters, AuctionResponse};
 use fungible::{FungibleOperation, FungibleResponse, FungibleTokenAbi};
-use linera_sdk::linera_base_types::{Account, AccountOwner, Amount, ApplicationId, ChainId, StreamUpdate, WithContractAbi};
+use linera_sdk::linera_base_types::{
+    Account, AccountOwner, Amount, ApplicationId, ChainId, CryptoHash, StreamUpdate, Timestamp,
+    WithContractAbi,
+};
 use linera_sdk::views::{RootView, View};
 use linera_sdk::{Contract, ContractRuntime};
 use shared::events::{AuctionEvent, ClearReason, AUCTION_STREAM};
 use shared::messages::AuctionMessage;
-use shared::types::{AuctionParams, BidRecord, SettlementResult};
+use shared::utils::{
+    asset_settlements, chain_bid_digest, convert_to_asset, convert_to_asset_floor, merge_settlement,
+    pro_rata_amount, pro_rata_units,
+};
+use shared::types::{
+    AssetSettlement, AuctionParams, BasketLeg, BasketLegFill, BidRecord, EndCondition, FillPolicy,
+    PaymentAsset, PaymentMethod, PaymentSource, PaymentTokenInfo, PoolStatus, PriceClock,
+    SettlementDelivery, SettlementResult, UnclaimedPolicy, UserCommitment,
+};
 
 pub struct AuctionContract {
     state: AuctionState,
@@ -40,6 +54,11 @@ impl Contract for AuctionContract {
         // Validate that the application parameters were configured correctly
         let app_params = self.runtime.application_parameters();
 
+        // The instantiating signer manages the payment token registry
+        // (create the application from the AAC chain so the registry lives there)
+        self.state.admin.set(self.runtime.authenticated_signer());
+        self.state.schema_version.set(SCHEMA_VERSION);
+
         // Emit initialization event to create the stream
         // This ensures the stream exists on every chain where the app is deployed
         let event = AuctionEvent::ApplicationInitialized {
@@ -83,27 +102,143 @@ impl Contract for AuctionContract {
                 self.handle_cancel_auction(auction_id).await
             }
 
+            AuctionOperation::ClaimProceeds { auction_id } => {
+                self.handle_claim_proceeds(auction_id).await
+            }
+
             AuctionOperation::PruneSettledAuction { auction_id } => {
                 self.handle_prune_settled_auction(auction_id).await
             }
 
-            AuctionOperation::Trigger {} => {
+            AuctionOperation::SweepUnclaimed { auction_id } => {
+                self.handle_sweep_unclaimed(auction_id).await;
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::Migrate { max_entries } => {
+                if self.runtime.chain_id() == self.runtime.application_parameters().aac_chain {
+                    self.assert_admin();
+                } else {
+                    // A user chain only holds its owners' commitments
+                    let signer = self
+                        .runtime
+                        .authenticated_signer()
+                        .expect("Migrate must be called by authenticated user");
+                    assert!(
+                        self.runtime.chain_ownership().verify_owner(&signer),
+                        "Only an owner of this chain can migrate its commitments"
+                    );
+                }
+                let entries = self.handle_migrate(max_entries as usize).await;
+                AuctionResponse::Migrated {
+                    entries: entries as u64,
+                    schema_version: *self.state.schema_version.get(),
+                }
+            }
+
+            AuctionOperation::SetUnclaimedPolicy { policy } => {
+                self.assert_admin();
+                self.state.unclaimed_policy.set(policy);
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::TransferAdmin { new_admin } => {
+                self.assert_admin();
+                self.state.admin.set(Some(new_admin));
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::AddPaymentToken {
+                token_app,
+                symbol,
+                decimals,
+            } => {
+                self.assert_admin();
+                let info = PaymentTokenInfo {
+                    token_app,
+                    symbol,
+                    decimals,
+                };
+                self.state.payment_tokens.insert(&token_app, info).unwrap();
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::RemovePaymentToken { token_app } => {
+                self.assert_admin();
+                self.state.payment_tokens.remove(&token_app).unwrap();
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::RegisterIndexer {
+                chain_id,
+                indexer_app,
+            } => {
+                self.assert_admin();
+                self.state.indexers.insert(&chain_id, indexer_app).unwrap();
+
+                let event = AuctionEvent::IndexerRegistered {
+                    indexer_chain: chain_id,
+                    indexer_app,
+                };
+                self.runtime.emit(AUCTION_STREAM.into(), &event);
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::UnregisterIndexer { chain_id } => {
+                self.assert_admin();
+                self.state.indexers.remove(&chain_id).unwrap();
+
+                let event = AuctionEvent::IndexerUnregistered {
+                    indexer_chain: chain_id,
+                };
+                self.runtime.emit(AUCTION_STREAM.into(), &event);
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::ConfirmArchive {
+                auction_id,
+                bid_count,
+                digest,
+            } => {
+                // The AAC checks the caller against the indexer application it registered
+                let indexer_app = self
+                    .runtime
+                    .authenticated_caller_id()
+                    .expect("ConfirmArchive must be called by the indexer application");
+                let aac_chain = self.runtime.application_parameters().aac_chain;
+                self.runtime
+                    .prepare_message(AuctionMessage::ArchiveConfirmed {
+                        auction_id,
+                        bid_count,
+                        digest,
+                        indexer_app,
+                    })
+                    .send_to(aac_chain);
                 AuctionResponse::Ok
             }
 
+            AuctionOperation::Trigger {} => {
+                self.handle_trigger().await
+            }
+
             // ═══════════════════════════════════════════════════════════
             // UIC CHAIN OPERATIONS
             // ═══════════════════════════════════════════════════════════
 
-            AuctionOperation::Buy { auction_id, quantity } => {
+            AuctionOperation::Buy {
+                auction_id,
+                quantity,
+                max_price_per_unit,
+                deadline,
+                fill_policy,
+                delivery,
+                payment_method,
+                payment_asset,
+                recipient,
+                payment_rate,
+            } => {
                 // Store local commitment (UIC state)
-                let mut commitment = self
-                    .state
-                    .my_commitments
-                    .get(&auction_id)
-                    .await
-                    .unwrap()
-                    .unwrap_or_default();
+                let mut commitment = self.load_commitment(auction_id).await;
 
                 commitment.total_quantity += quantity;
                 self.state
@@ -111,15 +246,104 @@ impl Contract for AuctionContract {
                     .insert(&auction_id, commitment)
                     .unwrap();
 
-                // Send message to AAC chain
                 let params = self.runtime.application_parameters();
                 let user_chain = self.runtime.chain_id();
+
+                // Paying from the user chain: move max_price × quantity, converted into the
+                // escrowed asset, into the AAC escrow first.
+                // The fungible Credit and the PlaceBid below leave in the same bundle, so the
+                // bid is only processed once the funds have arrived on the AAC.
+                let (payment, default_delivery) = match payment_method.unwrap_or_default() {
+                    PaymentMethod::AacBalance => (PaymentSource::AacBalance, SettlementDelivery::Aac),
+                    PaymentMethod::Deposit => (PaymentSource::Deposit, SettlementDelivery::Deposit),
+                    PaymentMethod::UserChain(escrow_asset) => {
+                        assert!(
+                            payment_asset.is_none_or(|asset| asset == escrow_asset),
+                            "payment_asset must match the asset escrowed from the user chain"
+                        );
+                        let max_price = max_price_per_unit
+                            .expect("Paying from the user chain requires max_price_per_unit");
+                        let amount = convert_to_asset(
+                            max_price.saturating_mul(quantity as u128),
+                            payment_rate.unwrap_or(Amount::ONE),
+                        );
+                        self.escrow_from_user_chain(escrow_asset, amount);
+
+                        (
+                            PaymentSource::Escrowed {
+                                payment_asset: escrow_asset,
+                                amount,
+                            },
+                            SettlementDelivery::UserChain,
+                        )
+                    }
+                };
+                let payment_asset = match payment {
+                    PaymentSource::Escrowed { payment_asset, .. } => Some(payment_asset),
+                    _ => payment_asset,
+                };
+
+                // Send message to AAC chain
                 self.runtime
                     .prepare_message(AuctionMessage::PlaceBid {
                         auction_id,
                         user_chain,
                         quantity,
+                        max_price_per_unit,
+                        deadline,
+                        fill_policy: fill_policy.unwrap_or_default(),
+                        submitted_at: self.runtime.system_time(),
+                        delivery: delivery.unwrap_or(default_delivery),
+                        payment,
+                        payment_asset,
+                        recipient,
+                    })
+                    .with_authentication()
+                    .with_tracking()
+                    .send_to(params.aac_chain);
+
+                AuctionResponse::BidSubmitted {
+                    auction_id,
+                    quantity,
+                }
+            }
+
+            AuctionOperation::BuyFor {
+                auction_id,
+                quantity,
+                beneficiary,
+                max_price_per_unit,
+                deadline,
+            } => {
+                // Store local commitment (UIC state)
+                let mut commitment = self.load_commitment(auction_id).await;
+
+                commitment.total_quantity += quantity;
+                self.state
+                    .my_commitments
+                    .insert(&auction_id, commitment)
+                    .unwrap();
+
+                let params = self.runtime.application_parameters();
+
+                // The AAC pulls payment from the beneficiary's allowance; the bid,
+                // its allocation and any refund belong to the beneficiary
+                self.runtime
+                    .prepare_message(AuctionMessage::PlaceBid {
+                        auction_id,
+                        user_chain: self.runtime.chain_id(),
+                        quantity,
+                        max_price_per_unit: Some(max_price_per_unit),
+                        deadline,
+                        fill_policy: FillPolicy::default(),
+                        submitted_at: self.runtime.system_time(),
+                        delivery: SettlementDelivery::Aac,
+                        payment: PaymentSource::Allowance { owner: beneficiary },
+                        payment_asset: None,
+                        recipient: None,
                     })
+                    .with_authentication()
+                    .with_tracking()
                     .send_to(params.aac_chain);
 
                 AuctionResponse::BidSubmitted {
@@ -128,6 +352,48 @@ impl Contract for AuctionContract {
                 }
             }
 
+            AuctionOperation::BuyBasket {
+                legs,
+                payment_method,
+            } => {
+                assert!(!legs.is_empty(), "Basket has no legs");
+
+                // Store local commitments (UIC state)
+                for leg in &legs {
+                    let mut commitment = self.load_commitment(leg.auction_id).await;
+
+                    commitment.total_quantity += leg.quantity;
+                    self.state
+                        .my_commitments
+                        .insert(&leg.auction_id, commitment)
+                        .unwrap();
+                }
+
+                let params = self.runtime.application_parameters();
+
+                // Payment is collected on the AAC so a failing leg can be rolled back
+                let (payment, delivery) = match payment_method.unwrap_or_default() {
+                    PaymentMethod::AacBalance => (PaymentSource::AacBalance, SettlementDelivery::Aac),
+                    PaymentMethod::Deposit => (PaymentSource::Deposit, SettlementDelivery::Deposit),
+                    PaymentMethod::UserChain(_) => {
+                        panic!("Baskets are paid from the AAC balance or the deposit ledger")
+                    }
+                };
+
+                self.runtime
+                    .prepare_message(AuctionMessage::BuyBasket {
+                        user_chain: self.runtime.chain_id(),
+                        legs,
+                        delivery,
+                        payment,
+                    })
+                    .with_authentication()
+                    .with_tracking()
+                    .send_to(params.aac_chain);
+
+                AuctionResponse::Ok
+            }
+
             AuctionOperation::SubscribeToAuction { aac_chain } => {
                 let app_id = self.runtime.application_id().forget_abi();
                 self.runtime.subscribe_to_events(
@@ -150,6 +416,255 @@ impl Contract for AuctionContract {
                 AuctionResponse::Ok
             }
 
+            AuctionOperation::Deposit {
+                payment_asset,
+                amount,
+            } => {
+                let app_params = self.runtime.application_parameters();
+                let owner = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("Deposit must be called by authenticated user");
+
+                if self.runtime.chain_id() == app_params.aac_chain {
+                    // Called on AAC - move the owner's AAC balance into escrow
+                    self.collect_payment(owner, amount, payment_asset)
+                        .expect("Deposit payment failed");
+                    self.credit_deposit(owner, payment_asset, amount).await;
+                } else {
+                    // Called on UIC - escrow from this chain, then credit the ledger on AAC.
+                    // Both messages leave in the same bundle.
+                    self.escrow_from_user_chain(payment_asset, amount);
+                    self.runtime
+                        .prepare_message(AuctionMessage::Deposit {
+                            payment_asset,
+                            amount,
+                        })
+                        .with_authentication()
+                        .with_tracking()
+                        .send_to(app_params.aac_chain);
+                }
+
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::Withdraw {
+                payment_asset,
+                amount,
+            } => {
+                let app_params = self.runtime.application_parameters();
+                let current_chain = self.runtime.chain_id();
+                let owner = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("Withdraw must be called by authenticated user");
+
+                if current_chain == app_params.aac_chain {
+                    // Called on AAC - pay out to the owner's AAC account
+                    self.handle_withdraw_deposit(owner, payment_asset, amount, current_chain)
+                        .await;
+                } else {
+                    // Called on UIC - ask AAC to pay out to this chain
+                    self.runtime
+                        .prepare_message(AuctionMessage::Withdraw {
+                            payment_asset,
+                            amount,
+                            user_chain: current_chain,
+                        })
+                        .with_authentication()
+                        .send_to(app_params.aac_chain);
+                }
+
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::AuthorizeBidder { bidder } => {
+                self.forward_bidder_authorisation(bidder, true);
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::RevokeBidder { bidder } => {
+                self.forward_bidder_authorisation(bidder, false);
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::OpenPool {
+                auction_id,
+                target_quantity,
+                max_price_per_unit,
+                payment_asset,
+            } => {
+                let app_params = self.runtime.application_parameters();
+                let organiser = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("OpenPool must be called by authenticated user");
+
+                if self.runtime.chain_id() == app_params.aac_chain {
+                    let pool_id = self
+                        .handle_open_pool(
+                            organiser,
+                            auction_id,
+                            target_quantity,
+                            max_price_per_unit,
+                            payment_asset,
+                        )
+                        .await;
+                    AuctionResponse::PoolOpened { pool_id }
+                } else {
+                    // Pool ID is assigned by the AAC (see the PoolOpened event)
+                    self.runtime
+                        .prepare_message(AuctionMessage::OpenPool {
+                            auction_id,
+                            target_quantity,
+                            max_price_per_unit,
+                            payment_asset,
+                        })
+                        .with_authentication()
+                        .send_to(app_params.aac_chain);
+                    AuctionResponse::Ok
+                }
+            }
+
+            AuctionOperation::ContributeToPool {
+                pool_id,
+                payment_asset,
+                amount,
+            } => {
+                let app_params = self.runtime.application_parameters();
+                let current_chain = self.runtime.chain_id();
+                let contributor = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("ContributeToPool must be called by authenticated user");
+
+                if current_chain == app_params.aac_chain {
+                    // Called on AAC - move the contributor's AAC balance into escrow
+                    self.collect_payment(contributor, amount, payment_asset)
+                        .expect("Pool contribution payment failed");
+                    self.handle_contribute_to_pool(
+                        contributor,
+                        current_chain,
+                        pool_id,
+                        payment_asset,
+                        amount,
+                    )
+                    .await
+                    .expect("Pool contribution rejected");
+                } else {
+                    // Called on UIC - escrow from this chain; both messages leave in the same bundle
+                    self.escrow_from_user_chain(payment_asset, amount);
+                    self.runtime
+                        .prepare_message(AuctionMessage::ContributeToPool {
+                            pool_id,
+                            payment_asset,
+                            amount,
+                        })
+                        .with_authentication()
+                        .with_tracking()
+                        .send_to(app_params.aac_chain);
+                }
+
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::PlacePoolBid { pool_id } => {
+                let app_params = self.runtime.application_parameters();
+                let organiser = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("PlacePoolBid must be called by authenticated user");
+
+                if self.runtime.chain_id() == app_params.aac_chain {
+                    self.handle_place_pool_bid(organiser, pool_id).await;
+                } else {
+                    self.runtime
+                        .prepare_message(AuctionMessage::PlacePoolBid { pool_id })
+                        .with_authentication()
+                        .send_to(app_params.aac_chain);
+                }
+
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::CancelPool { pool_id } => {
+                let app_params = self.runtime.application_parameters();
+                let organiser = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("CancelPool must be called by authenticated user");
+
+                if self.runtime.chain_id() == app_params.aac_chain {
+                    self.handle_cancel_pool(organiser, pool_id).await;
+                } else {
+                    self.runtime
+                        .prepare_message(AuctionMessage::CancelPool { pool_id })
+                        .with_authentication()
+                        .send_to(app_params.aac_chain);
+                }
+
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::ClaimPoolShare { pool_id } => {
+                let app_params = self.runtime.application_parameters();
+                let contributor = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("ClaimPoolShare must be called by authenticated user");
+
+                if self.runtime.chain_id() == app_params.aac_chain {
+                    self.handle_claim_pool_share(contributor, pool_id).await;
+                } else {
+                    self.runtime
+                        .prepare_message(AuctionMessage::ClaimPoolShare { pool_id })
+                        .with_authentication()
+                        .send_to(app_params.aac_chain);
+                }
+
+                AuctionResponse::Ok
+            }
+
+            AuctionOperation::ClaimAll { auction_ids } => {
+                let app_params = self.runtime.application_parameters();
+                let current_chain = self.runtime.chain_id();
+
+                if current_chain == app_params.aac_chain {
+                    // AAC_chain should not bid on auctions
+                    return AuctionResponse::Ok;
+                }
+
+                // Default to every auction this chain bid on that has no settlement yet
+                let auction_ids = match auction_ids {
+                    Some(auction_ids) => auction_ids,
+                    None => {
+                        let mut auction_ids = self.state.my_commitments.indices().await.unwrap();
+                        auction_ids.extend(self.state.legacy_commitments.indices().await.unwrap());
+
+                        let mut unsettled = Vec::new();
+                        for auction_id in auction_ids {
+                            let commitment = self.load_commitment(auction_id).await;
+                            if commitment.total_quantity > 0 && commitment.settlement.is_none() {
+                                unsettled.push(auction_id);
+                            }
+                        }
+                        unsettled
+                    }
+                };
+
+                if !auction_ids.is_empty() {
+                    self.runtime
+                        .prepare_message(AuctionMessage::ClaimAll {
+                            auction_ids,
+                            user_chain: current_chain,
+                        })
+                        .with_authentication()
+                        .send_to(app_params.aac_chain);
+                }
+
+                AuctionResponse::Ok
+            }
+
             AuctionOperation::ClaimSettlement { auction_id } => {
                 let app_params = self.runtime.application_parameters();
                 let current_chain = self.runtime.chain_id();
@@ -166,6 +681,7 @@ impl Contract for AuctionContract {
                             auction_id,
                             user_chain: current_chain,
                         })
+                        .with_authentication()
                         .send_to(app_params.aac_chain);
 
                     AuctionResponse::Ok
@@ -175,6 +691,21 @@ impl Contract for AuctionContract {
     }
 
     async fn execute_message(&mut self, message: Self::Message) {
+        let is_bouncing = self
+            .runtime
+            .message_is_bouncing()
+            .expect("Message delivery status has to be available when executing a message");
+        if is_bouncing {
+            self.handle_bounced_message(message).await;
+            return;
+        }
+
+        // The chain that actually sent the message (never trust an embedded chain ID)
+        let origin_chain = self
+            .runtime
+            .message_origin_chain_id()
+            .expect("Incoming message origin chain ID has to be available when executing a message");
+
         match message {
             // ═══════════════════════════════════════════════════════════
             // MESSAGES RECEIVED BY AAC CHAIN
@@ -189,48 +720,363 @@ impl Contract for AuctionContract {
                 auction_id,
                 user_chain,
             } => {
+                assert_eq!(
+                    user_chain, origin_chain,
+                    "ClaimSettlement must come from the claiming chain"
+                );
                 self.handle_claim_settlement(auction_id, user_chain).await;
             }
 
-            AuctionMessage::PlaceBid {
+            AuctionMessage::ArchiveConfirmed {
                 auction_id,
+                bid_count,
+                digest,
+                indexer_app,
+            } => {
+                self.handle_archive_confirmed(
+                    auction_id,
+                    origin_chain,
+                    indexer_app,
+                    bid_count,
+                    digest,
+                )
+                .await;
+            }
+
+            AuctionMessage::ClaimAll {
+                auction_ids,
                 user_chain,
-                quantity,
             } => {
-                // Delegate to helper function for cleaner code organization
-                self.handle_place_bid(auction_id, user_chain, quantity).await;
+                assert_eq!(
+                    user_chain, origin_chain,
+                    "ClaimAll must come from the claiming chain"
+                );
+                self.handle_claim_all(auction_ids, user_chain).await;
             }
 
-            // ═══════════════════════════════════════════════════════════
-            // MESSAGES RECEIVED BY UIC CHAINS
-            // ═══════════════════════════════════════════════════════════
+            AuctionMessage::OpenPool {
+                auction_id,
+                target_quantity,
+                max_price_per_unit,
+                payment_asset,
+            } => {
+                let organiser = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("OpenPool must be authenticated");
+                self.handle_open_pool(
+                    organiser,
+                    auction_id,
+                    target_quantity,
+                    max_price_per_unit,
+                    payment_asset,
+                )
+                .await;
+            }
 
-            AuctionMessage::SettlementResult { auction_id, result } => {
-                // Received on UIC chain from AAC chain
-                let mut commitment = self
-                    .state
-                    .my_commitments
-                    .get(&auction_id)
-                    .await
-                    .unwrap()
-                    .unwrap_or_default();
+            AuctionMessage::ContributeToPool {
+                pool_id,
+                payment_asset,
+                amount,
+            } => {
+                // Funds were credited to the escrow earlier in the same bundle
+                let contributor = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("ContributeToPool must be authenticated");
+                let result = self
+                    .handle_contribute_to_pool(
+                        contributor,
+                        origin_chain,
+                        pool_id,
+                        payment_asset,
+                        amount,
+                    )
+                    .await;
+
+                // Return the escrowed funds if the pool cannot take them
+                if result.is_err() {
+                    let refund_account = Account {
+                        chain_id: origin_chain,
+                        owner: contributor,
+                    };
+                    let escrow_owner = self.runtime.application_id().into();
+                    self.transfer_asset(payment_asset, escrow_owner, amount, refund_account)
+                        .expect("Failed to return rejected pool contribution");
+                }
+            }
 
-                commitment.settlement = Some(result);
-                self.state
-                    .my_commitments
-                    .insert(&auction_id, commitment)
-                    .unwrap();
+            AuctionMessage::PlacePoolBid { pool_id } => {
+                let organiser = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("PlacePoolBid must be authenticated");
+                self.handle_place_pool_bid(organiser, pool_id).await;
             }
-        }
-    }
 
-    async fn process_streams(&mut self, updates: Vec<StreamUpdate>) {
-        for update in updates {
-            assert_eq!(update.stream_id.stream_name, AUCTION_STREAM.into());
-            assert_eq!(
-                update.stream_id.application_id,
-                self.runtime.application_id().forget_abi().into()
-            );
+            AuctionMessage::CancelPool { pool_id } => {
+                let organiser = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("CancelPool must be authenticated");
+                self.handle_cancel_pool(organiser, pool_id).await;
+            }
+
+            AuctionMessage::ClaimPoolShare { pool_id } => {
+                let contributor = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("ClaimPoolShare must be authenticated");
+                self.handle_claim_pool_share(contributor, pool_id).await;
+            }
+
+            AuctionMessage::Deposit {
+                payment_asset,
+                amount,
+            } => {
+                // Funds were credited to the escrow earlier in the same bundle
+                let owner = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("Deposit must be authenticated");
+                self.credit_deposit(owner, payment_asset, amount).await;
+            }
+
+            AuctionMessage::Withdraw {
+                payment_asset,
+                amount,
+                user_chain,
+            } => {
+                let owner = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("Withdraw must be authenticated");
+                self.handle_withdraw_deposit(owner, payment_asset, amount, user_chain)
+                    .await;
+            }
+
+            AuctionMessage::AuthorizeBidder { bidder } => {
+                let beneficiary = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("AuthorizeBidder must be authenticated");
+                self.set_bidder_authorisation(beneficiary, bidder, true);
+            }
+
+            AuctionMessage::RevokeBidder { bidder } => {
+                let beneficiary = self
+                    .runtime
+                    .authenticated_signer()
+                    .expect("RevokeBidder must be authenticated");
+                self.set_bidder_authorisation(beneficiary, bidder, false);
+            }
+
+            AuctionMessage::PlaceBid {
+                auction_id,
+                user_chain,
+                quantity,
+                max_price_per_unit,
+                deadline,
+                fill_policy,
+                submitted_at,
+                delivery,
+                payment,
+                payment_asset,
+                recipient,
+            } => {
+                // A BuyFor bid spends another owner's allowance: only that owner or a bidder
+                // it authorised may place one, and only under a price cap
+                let delegation_rejection = match payment {
+                    PaymentSource::Allowance { owner } => {
+                        let signer = self
+                            .runtime
+                            .authenticated_signer()
+                            .expect("PlaceBid must be authenticated");
+                        let authorised = signer == owner
+                            || self
+                                .state
+                                .authorised_bidders
+                                .contains_key(&(owner, signer))
+                                .await
+                                .unwrap();
+                        if !authorised {
+                            Some(format!("{} is not authorised to bid for {}", signer, owner))
+                        } else if max_price_per_unit.is_none() {
+                            Some("BuyFor bids require max_price_per_unit".to_string())
+                        } else {
+                            None
+                        }
+                    }
+                    _ => None,
+                };
+
+                // A bid naming another chain is rejected; any escrow goes back to the real sender
+                let accepted = if user_chain != origin_chain {
+                    let event = AuctionEvent::BidRejected {
+                        auction_id,
+                        user_chain: origin_chain,
+                        reason: format!(
+                            "PlaceBid names chain {} but was sent from {}",
+                            user_chain, origin_chain
+                        ),
+                    };
+                    self.runtime.emit(AUCTION_STREAM.into(), &event);
+                    false
+                } else if matches!(payment, PaymentSource::Pool { .. }) {
+                    // Pool funds are only spent by PlacePoolBid on the AAC
+                    let event = AuctionEvent::BidRejected {
+                        auction_id,
+                        user_chain,
+                        reason: "Pool payments cannot be sent in a PlaceBid".to_string(),
+                    };
+                    self.runtime.emit(AUCTION_STREAM.into(), &event);
+                    false
+                } else if let Some(reason) = delegation_rejection {
+                    let event = AuctionEvent::BidRejected {
+                        auction_id,
+                        user_chain,
+                        reason,
+                    };
+                    self.runtime.emit(AUCTION_STREAM.into(), &event);
+                    false
+                } else {
+                    // Delegate to helper function for cleaner code organization
+                    self.handle_place_bid(
+                        auction_id,
+                        user_chain,
+                        quantity,
+                        max_price_per_unit,
+                        deadline,
+                        fill_policy,
+                        submitted_at,
+                        delivery,
+                        payment,
+                        payment_asset,
+                        recipient,
+                    )
+                    .await
+                    .is_ok()
+                };
+
+                // Let the beneficiary find (and claim) the position from its own chain
+                if let PaymentSource::Allowance { owner } = payment {
+                    if accepted {
+                        self.record_delegated_position(owner, auction_id, user_chain)
+                            .await;
+                    }
+                }
+
+                // Return funds escrowed from the user chain if the bid was rejected
+                if let PaymentSource::Escrowed { payment_asset, amount } = payment {
+                    if !accepted {
+                        let bidder = self
+                            .runtime
+                            .authenticated_signer()
+                            .expect("PlaceBid must be authenticated");
+                        let refund_account = Account {
+                            chain_id: origin_chain,
+                            owner: bidder,
+                        };
+                        self.refund_payment(auction_id, refund_account, amount, payment_asset);
+                    }
+                }
+            }
+
+            AuctionMessage::BuyBasket {
+                user_chain,
+                legs,
+                delivery,
+                payment,
+            } => {
+                assert_eq!(
+                    user_chain, origin_chain,
+                    "BuyBasket must be sent from the chain it names"
+                );
+                assert!(
+                    matches!(payment, PaymentSource::AacBalance | PaymentSource::Deposit),
+                    "Baskets are paid from the AAC balance or the deposit ledger"
+                );
+                self.handle_buy_basket(user_chain, legs, delivery, payment)
+                    .await;
+            }
+
+            // ═══════════════════════════════════════════════════════════
+            // MESSAGES RECEIVED BY UIC CHAINS
+            // ═══════════════════════════════════════════════════════════
+
+            AuctionMessage::SettlementResult { auction_id, result } => {
+                // Received on UIC chain from AAC chain
+                assert_eq!(
+                    origin_chain,
+                    self.runtime.application_parameters().aac_chain,
+                    "SettlementResult must come from the AAC chain"
+                );
+                let mut commitment = self.load_commitment(auction_id).await;
+
+                // Positions on several chains (BuyFor) settle separately; keep their sum
+                if result.gifted_from.is_some() {
+                    commitment.gifts_received.push(result);
+                } else if let Some(settlement) = commitment.settlement.as_mut() {
+                    merge_settlement(settlement, result);
+                } else {
+                    commitment.settlement = Some(result);
+                }
+                self.state
+                    .my_commitments
+                    .insert(&auction_id, commitment)
+                    .unwrap();
+            }
+
+            AuctionMessage::SettlementResults { results } => {
+                // Received on UIC chain from AAC chain (answer to ClaimAll)
+                assert_eq!(
+                    origin_chain,
+                    self.runtime.application_parameters().aac_chain,
+                    "SettlementResults must come from the AAC chain"
+                );
+                for (auction_id, result) in results {
+                    let mut commitment = self.load_commitment(auction_id).await;
+
+                    match commitment.settlement.as_mut() {
+                        Some(settlement) => merge_settlement(settlement, result),
+                        None => commitment.settlement = Some(result),
+                    }
+                    self.state
+                        .my_commitments
+                        .insert(&auction_id, commitment)
+                        .unwrap();
+                }
+            }
+
+            AuctionMessage::PoolShareSettled {
+                pool_id: _,
+                auction_id,
+                result,
+            } => {
+                // Received on UIC chain from AAC chain
+                assert_eq!(
+                    origin_chain,
+                    self.runtime.application_parameters().aac_chain,
+                    "PoolShareSettled must come from the AAC chain"
+                );
+                let mut commitment = self.load_commitment(auction_id).await;
+
+                commitment.pool_shares.push(result);
+                self.state
+                    .my_commitments
+                    .insert(&auction_id, commitment)
+                    .unwrap();
+            }
+        }
+    }
+
+    async fn process_streams(&mut self, updates: Vec<StreamUpdate>) {
+        for update in updates {
+            assert_eq!(update.stream_id.stream_name, AUCTION_STREAM.into());
+            assert_eq!(
+                update.stream_id.application_id,
+                self.runtime.application_id().forget_abi().into()
+            );
 
             for index in update.new_indices() {
                 let _event: AuctionEvent =
@@ -262,6 +1108,38 @@ impl AuctionContract {
             "Only AAC chain can create auctions"
         );
 
+        // Submission-time pricing needs a clock the UIC can observe
+        assert!(
+            params.fair_ordering_window.is_none() || params.price_clock == PriceClock::WallClock,
+            "Fairness mode requires the WallClock price clock"
+        );
+
+        // Fungible payment tokens must be in the registry - an arbitrary token app
+        // could report a transfer without moving any funds
+        let assets = std::iter::once(params.payment_asset)
+            .chain(params.accepted_assets.iter().map(|accepted| accepted.asset));
+        for asset in assets {
+            if let PaymentAsset::Fungible(token_app) = asset {
+                assert!(
+                    self.state.payment_tokens.contains_key(&token_app).await.unwrap(),
+                    "Payment token {} is not registered",
+                    token_app
+                );
+            }
+        }
+
+        // Each extra asset needs a usable rate and must not repeat the unit of account
+        for (index, accepted) in params.accepted_assets.iter().enumerate() {
+            assert!(accepted.rate > Amount::ZERO, "Accepted asset rate must be positive");
+            assert!(
+                accepted.asset != params.payment_asset
+                    && params.accepted_assets[..index]
+                        .iter()
+                        .all(|other| other.asset != accepted.asset),
+                "Accepted assets must be distinct from each other and from payment_asset"
+            );
+        }
+
         // Auto-generate auction ID
         let auction_id = *self.state.next_auction_id.get();
         self.state.next_auction_id.set(auction_id + 1);
@@ -269,6 +1147,7 @@ impl AuctionContract {
         let auction = AuctionData::new(params.clone(), self.runtime.system_time());
 
         self.state.auctions.insert(&auction_id, auction).unwrap();
+        self.state.open_auctions.insert(&auction_id, ()).unwrap();
 
         // Emit creation event with full params
         let event = AuctionEvent::AuctionCreated {
@@ -282,13 +1161,164 @@ impl AuctionContract {
             start_time: params.start_time,
             end_time: params.end_time,
             creator: params.creator,
-            payment_token_app: params.payment_token_app,
+            payment_asset: params.payment_asset,
+            accepted_assets: params.accepted_assets.clone(),
+            price_clock: params.price_clock,
+            block_height: self.runtime.block_height(),
         };
         self.runtime.emit(AUCTION_STREAM.into(), &event);
 
         AuctionResponse::AuctionCreated { auction_id }
     }
 
+    /// Assert the operation runs on the AAC and is signed by the registry admin
+    /// Deployments instantiated without a signer (or before the admin was recorded) have no
+    /// admin yet: the first owner of the AAC chain to run an admin operation becomes it.
+    fn assert_admin(&mut self) {
+        assert_eq!(
+            self.runtime.chain_id(),
+            self.runtime.application_parameters().aac_chain,
+            "Protocol settings are managed on the AAC chain"
+        );
+        let signer = self
+            .runtime
+            .authenticated_signer()
+            .expect("Admin operations must be authenticated");
+
+        match *self.state.admin.get() {
+            Some(admin) => assert_eq!(signer, admin, "Only the admin can change protocol settings"),
+            None => {
+                assert!(
+                    self.runtime.chain_ownership().verify_owner(&signer),
+                    "Only an owner of the AAC chain can take the unset admin role"
+                );
+                self.state.admin.set(Some(signer));
+            }
+        }
+    }
+
+    /// Record an indexer's archive confirmation if it matches the settled auction (AAC only)
+    /// The indexer must be registered and hold every accepted bid, in order.
+    async fn handle_archive_confirmed(
+        &mut self,
+        auction_id: u64,
+        indexer_chain: ChainId,
+        indexer_app: ApplicationId,
+        bid_count: u64,
+        digest: Option<CryptoHash>,
+    ) {
+        let registered_app = self.state.indexers.get(&indexer_chain).await.unwrap();
+        let auction = self.state.auctions.get_mut(&auction_id).await.unwrap();
+
+        let rejection = match auction {
+            _ if registered_app.is_none() => Some("Indexer chain is not registered".to_string()),
+            _ if registered_app != Some(indexer_app) => {
+                Some("Caller is not the registered indexer application".to_string())
+            }
+            None => Some("Auction not found".to_string()),
+            Some(auction) if auction.status != shared::types::AuctionStatus::Settled => {
+                Some("Auction not settled".to_string())
+            }
+            Some(auction) if bid_count != auction.total_bids => Some(format!(
+                "Indexer holds {} bids, auction has {}",
+                bid_count, auction.total_bids
+            )),
+            // The digest covers the bids after the ones migrated from the legacy layout
+            Some(auction) if digest != auction.bid_digest => {
+                Some("Bid digest does not match the auction's bid log".to_string())
+            }
+            Some(auction) => {
+                auction.archive_confirmed = true;
+                None
+            }
+        };
+
+        let event = match rejection {
+            Some(reason) => AuctionEvent::ArchiveRejected {
+                auction_id,
+                indexer_chain,
+                reason,
+            },
+            None => AuctionEvent::ArchiveConfirmed {
+                auction_id,
+                indexer_chain,
+            },
+        };
+        self.runtime.emit(AUCTION_STREAM.into(), &event);
+    }
+
+    /// Pay a settled auction's sale proceeds out of escrow to the creator's AAC account
+    /// (AAC only, creator only, once)
+    /// Proceeds are sold × clearing_price converted into each asset bids paid with; everything
+    /// above that stays in escrow for bidder refunds.
+    async fn handle_claim_proceeds(&mut self, auction_id: u64) -> AuctionResponse {
+        assert_eq!(
+            self.runtime.chain_id(),
+            self.runtime.application_parameters().aac_chain,
+            "Only AAC chain can pay auction proceeds"
+        );
+        self.assert_migrated().await;
+
+        let auction = self
+            .state
+            .auctions
+            .get(&auction_id)
+            .await
+            .expect("Failed to get auction")
+            .expect("Auction not found");
+
+        let authenticated_signer = self
+            .runtime
+            .authenticated_signer()
+            .expect("Caller must be authenticated to claim proceeds");
+        assert_eq!(
+            authenticated_signer, auction.params.creator,
+            "Only the creator can claim the auction's proceeds"
+        );
+        assert_eq!(
+            auction.status,
+            shared::types::AuctionStatus::Settled,
+            "Auction not settled"
+        );
+        assert!(!auction.proceeds_claimed, "Proceeds already claimed");
+
+        let clearing_price = auction.clearing_price.expect("Clearing price not set");
+        let creator_account = Account {
+            chain_id: self.runtime.chain_id(),
+            owner: auction.params.creator,
+        };
+        let escrow_owner = self.runtime.application_id().into();
+        for sold in &auction.sold_by_asset {
+            let rate = auction
+                .params
+                .rate_for(sold.payment_asset)
+                .expect("Sold units paid in an accepted asset");
+            let proceeds = convert_to_asset_floor(
+                clearing_price.saturating_mul(sold.quantity as u128),
+                rate,
+            );
+            if proceeds == Amount::ZERO {
+                continue;
+            }
+
+            self.transfer_asset(sold.payment_asset, escrow_owner, proceeds, creator_account)
+                .expect("Failed to pay proceeds to creator");
+
+            let event = AuctionEvent::ProceedsPaid {
+                auction_id,
+                creator: auction.params.creator,
+                payment_asset: sold.payment_asset,
+                amount: proceeds,
+            };
+            self.runtime.emit(AUCTION_STREAM.into(), &event);
+        }
+
+        let auction_mut = self.state.auctions.get_mut(&auction_id).await.unwrap().unwrap();
+        auction_mut.proceeds_claimed = true;
+
+        AuctionResponse::Ok
+    }
+
     /// Handle auction cancellation by creator (before start, AAC only)
     async fn handle_cancel_auction(&mut self, auction_id: u64) -> AuctionResponse {
         // Validate on AAC chain
@@ -297,6 +1327,7 @@ impl AuctionContract {
             self.runtime.application_parameters().aac_chain,
             "Only AAC chain can cancel auctions"
         );
+        self.assert_migrated().await;
 
         let auction = self
             .state
@@ -326,6 +1357,7 @@ impl AuctionContract {
         // Update auction status to Cancelled
         let auction_mut = self.state.auctions.get_mut(&auction_id).await.unwrap().unwrap();
         auction_mut.status = shared::types::AuctionStatus::Cancelled;
+        self.state.open_auctions.remove(&auction_id).unwrap();
 
         // Emit cancellation event
         let reason = format!(
@@ -361,7 +1393,6 @@ impl AuctionContract {
 
         // Calculate elapsed time since settlement
         let one_hour_micros = 60 * 60 * 1_000_000u64;
-        let ninety_days_micros = 90 * 24 * 60 * 60 * 1_000_000u64;
         let settled_at = auction.settled_at.expect("Settled time not set");
         let elapsed = self.runtime.system_time().delta_since(settled_at).as_micros();
 
@@ -371,54 +1402,69 @@ impl AuctionContract {
             "Auction settled less than 1 hour ago. Cannot prune yet."
         );
 
-        // Two-tier pruning strategy
-        let prune_all = elapsed >= ninety_days_micros;
+        self.assert_migrated().await;
 
-        // Iterate over all user-auction combinations to find bids for this auction
-        let all_keys: Vec<(ChainId, u64)> = self.state.user_auction_bids.indices().await.unwrap();
+        // The records are only deleted once an indexer holds a copy of them
+        assert!(
+            auction.archive_confirmed,
+            "No registered indexer has confirmed the auction's archive. Cannot prune yet."
+        );
 
-        for (user_chain, auction_id_key) in all_keys {
-            if auction_id_key == auction_id {
-                let user_bids = self
-                    .state
-                    .user_auction_bids
-                    .get(&(user_chain, auction_id))
-                    .await
-                    .unwrap()
-                    .unwrap_or_default();
+        // Two-tier pruning strategy: unclaimed bids hold users' claim data until swept
+        let prune_all = auction.unclaimed_swept;
+
+        // Tier 1 rewrites bidder_positions, which would shift a running sweep's
+        // or auto-distribution's cursor
+        assert!(
+            prune_all || auction.sweep_cursor == 0,
+            "SweepUnclaimed is in progress. Cannot prune until it completes."
+        );
+        assert!(
+            prune_all || auction.distribution_cursor == 0 || auction.distribution_complete,
+            "Settlements are still being distributed. Cannot prune until they complete."
+        );
+
+        // Visit only this auction's positions
+        let mut remaining = Vec::new();
 
+        for (user_chain, bidder) in self.bidder_positions(auction_id).await {
+            let key = (user_chain, auction_id, bidder);
+            // Tier 1 (before SweepUnclaimed): Prune only claimed positions
+            // Tier 2 (after SweepUnclaimed): Prune every position
+            if prune_all || !self.is_unclaimed(auction_id, user_chain, bidder).await {
                 if prune_all {
-                    // Tier 2 (90+ days): Prune all bids for this user-auction
                     self.state
-                        .user_auction_bids
-                        .remove(&(user_chain, auction_id))
+                        .delegated_positions
+                        .remove(&(bidder, auction_id))
                         .unwrap();
-                } else {
-                    // Tier 1 (1hr - 90 days): Prune only claimed bids
-                    let mut filtered_bids = user_bids;
-                    filtered_bids.retain(|bid| !bid.claimed);
-
-                    if filtered_bids.is_empty() {
-                        // Remove entry if all bids were pruned
-                        self.state
-                            .user_auction_bids
-                            .remove(&(user_chain, auction_id))
-                            .unwrap();
-                    } else {
-                        // Update with remaining bids
-                        self.state
-                            .user_auction_bids
-                            .insert(&(user_chain, auction_id), filtered_bids)
-                            .unwrap();
-                    }
                 }
+                self.unindex_gifts(key).await;
+                self.state.positions.remove(&key).unwrap();
+            } else {
+                remaining.push((user_chain, bidder));
             }
         }
 
+        // Keep the index in step with the positions left
+        if remaining.is_empty() {
+            self.state.bidder_positions.remove(&auction_id).unwrap();
+        } else {
+            self.state
+                .bidder_positions
+                .insert(&auction_id, remaining)
+                .unwrap();
+        }
+
+        // The bid log goes only once nobody can still claim against it
+        if prune_all {
+            self.state.bid_log.remove_entry(&auction_id).unwrap();
+        }
+
         // Update auction to mark bids as pruned (if all were pruned)
         if prune_all {
             let auction_mut = self.state.auctions.get_mut(&auction_id).await.unwrap().unwrap();
             auction_mut.bids_pruned = true;
+            self.state.pending_distributions.remove(&auction_id).unwrap();
         }
 
         AuctionResponse::Ok
@@ -428,6 +1474,69 @@ impl AuctionContract {
     // Message Handlers
     // ═══════════════════════════════════════════════════════════
 
+    /// Handle a tracked message that the AAC rejected and bounced back (UIC processes this)
+    /// Funds escrowed in the same bundle bounce back separately through their own Credit.
+    async fn handle_bounced_message(&mut self, message: AuctionMessage) {
+        match message {
+            AuctionMessage::PlaceBid {
+                auction_id,
+                quantity,
+                ..
+            } => {
+                // The bid never reached the auction - undo the local commitment
+                self.release_commitment(auction_id, quantity).await;
+            }
+            AuctionMessage::BuyBasket { legs, .. } => {
+                for leg in legs {
+                    self.release_commitment(leg.auction_id, leg.quantity).await;
+                }
+            }
+            _ => {
+                // Nothing to undo locally
+            }
+        }
+    }
+
+    /// Read the local commitment for an auction (UIC only)
+    /// A commitment still in the original layout is migrated into my_commitments first,
+    /// so it is never shadowed by a new one written before Migrate reaches it.
+    async fn load_commitment(&mut self, auction_id: u64) -> UserCommitment {
+        if let Some(legacy) = self.state.legacy_commitments.get(&auction_id).await.unwrap() {
+            let commitment = legacy.into_commitment();
+            self.state
+                .my_commitments
+                .insert(&auction_id, commitment.clone())
+                .unwrap();
+            self.state.legacy_commitments.remove(&auction_id).unwrap();
+            return commitment;
+        }
+        self.state
+            .my_commitments
+            .get(&auction_id)
+            .await
+            .unwrap()
+            .unwrap_or_default()
+    }
+
+    /// Remove `quantity` from the local commitment for a bid that never reached the AAC
+    async fn release_commitment(&mut self, auction_id: u64, quantity: u64) {
+        let mut commitment = self.load_commitment(auction_id).await;
+
+        commitment.total_quantity = commitment.total_quantity.saturating_sub(quantity);
+        if commitment.total_quantity == 0
+            && commitment.settlement.is_none()
+            && commitment.gifts_received.is_empty()
+            && commitment.pool_shares.is_empty()
+        {
+            self.state.my_commitments.remove(&auction_id).unwrap();
+        } else {
+            self.state
+                .my_commitments
+                .insert(&auction_id, commitment)
+                .unwrap();
+        }
+    }
+
     /// Handle settlement claim from user chain (AAC processes this)
     async fn handle_claim_settlement(&mut self, auction_id: u64, user_chain: ChainId) {
         // Verify auction is settled
@@ -444,90 +1553,1034 @@ impl AuctionContract {
             shared::types::AuctionStatus::Settled,
             "Auction not settled yet"
         );
+        self.assert_migrated().await;
 
-        let clearing_price = auction.clearing_price.expect("Clearing price not set");
+        // Only the owner who paid can claim: settle just the signer's unclaimed bids
+        let bidder = self
+            .runtime
+            .authenticated_signer()
+            .expect("ClaimSettlement must be authenticated");
 
-        // O(1) lookup: Get all unclaimed bids for this user and auction
-        let mut user_bids = self
-            .state
-            .user_auction_bids
-            .get(&(user_chain, auction_id))
-            .await
-            .unwrap()
-            .unwrap_or_default();
+        // Nothing to claim (never bid or already claimed) is a no-op, not an error
+        for (position_chain, owner) in
+            self.claimable_positions(bidder, auction_id, user_chain).await
+        {
+            self.settle_claim(&auction, auction_id, position_chain, owner, user_chain)
+                .await;
+        }
 
-        // Calculate totals from unclaimed bids
-        let mut total_quantity = 0u64;
-        let mut total_paid = Amount::ZERO;
-        let mut has_unclaimed = false;
+        // Units others bought for the signer on this chain
+        let recipient = Account {
+            chain_id: user_chain,
+            owner: bidder,
+        };
+        self.claim_gifts(&auction, auction_id, recipient).await;
+    }
 
-        for bid in &user_bids {
-            if !bid.claimed {
-                total_quantity += bid.quantity;
-                total_paid = total_paid.saturating_add(bid.amount_paid);
-                has_unclaimed = true;
-            }
-        }
+    /// Settle one bidder's unclaimed bids: refunds and the SettlementResult message
+    /// The result goes to `notify_chain`, the chain claiming the position held on `user_chain`.
+    /// Returns false if the bidder had nothing to claim
+    async fn settle_claim(
+        &mut self,
+        auction: &AuctionData,
+        auction_id: u64,
+        user_chain: ChainId,
+        bidder: AccountOwner,
+        notify_chain: ChainId,
+    ) -> bool {
+        let Some((result, delivery)) = self
+            .claim_bids(auction, auction_id, user_chain, bidder)
+            .await
+        else {
+            return false;
+        };
 
-        // Check if user has any unclaimed bids
-        if !has_unclaimed {
-            // No unclaimed bids - either never bid or already claimed
-            return;
+        // Execute refunds in each asset (ledger credit, synchronous transfer on AAC, or cross-chain Credit)
+        for payment in &result.payments {
+            self.deliver_refund(
+                auction_id,
+                bidder,
+                user_chain,
+                delivery,
+                payment.refund,
+                payment.payment_asset,
+            )
+            .await;
         }
 
-        // Calculate settlement
-        let total_cost = clearing_price.saturating_mul(total_quantity as u128);
-        let refund = total_paid.saturating_sub(total_cost);
+        // Send settlement result to user
+        self.runtime
+            .prepare_message(AuctionMessage::SettlementResult { auction_id, result })
+            .send_to(notify_chain);
 
-        // Get authenticated bidder (for refund recipient)
-        let bidder = self
-            .runtime
-            .authenticated_signer()
-            .expect("ClaimSettlement must be authenticated");
+        true
+    }
+
+    /// Settle the next page of bids still unclaimed after the auction's claim window
+    /// Refunds are pushed to bidders or forfeited to the treasury per the unclaimed policy;
+    /// once the last page is done the auction's bid records can be pruned.
+    async fn handle_sweep_unclaimed(&mut self, auction_id: u64) {
+        let auction = self
+            .state
+            .auctions
+            .get(&auction_id)
+            .await
+            .expect("Failed to get auction")
+            .expect("Auction not found");
 
-        // Get payment token app for refund transfer
-        let payment_token_app = auction.params.payment_token_app;
+        assert_eq!(
+            auction.status,
+            shared::types::AuctionStatus::Settled,
+            "Auction not settled"
+        );
+        self.assert_migrated().await;
+        assert!(!auction.unclaimed_swept, "Unclaimed settlements already swept");
 
-        // Drop auction reference before function call
-        drop(auction);
+        let settled_at = auction.settled_at.expect("Settled time not set");
+        let elapsed = self.runtime.system_time().delta_since(settled_at).as_micros();
+        assert!(
+            elapsed >= auction.params.claim_window,
+            "Claim window still open. Bidders can claim until {} microseconds after settlement",
+            auction.params.claim_window
+        );
+
+        // One bounded page of positions per call, like auto-distribution
+        const SWEEP_BUDGET: usize = 25;
+        let cursor = auction.sweep_cursor as usize;
+        let positions = self.bidder_positions(auction_id).await;
+        let page: Vec<(ChainId, AccountOwner)> =
+            positions.iter().skip(cursor).take(SWEEP_BUDGET).copied().collect();
+
+        let policy = *self.state.unclaimed_policy.get();
+        let mut bidders_swept = 0u64;
+        let mut forfeited: Vec<(PaymentAsset, Amount)> = Vec::new();
+
+        for (user_chain, bidder) in page.iter().copied() {
+            if !self.is_unclaimed(auction_id, user_chain, bidder).await {
+                continue;
+            }
+
+            bidders_swept += 1;
+            match policy {
+                UnclaimedPolicy::PushToBidders => {
+                    self.settle_claim(&auction, auction_id, user_chain, bidder, user_chain)
+                        .await;
+                }
+                UnclaimedPolicy::ForfeitToTreasury(_) => {
+                    let (mut result, _) = self
+                        .claim_bids(&auction, auction_id, user_chain, bidder)
+                        .await
+                        .expect("Unclaimed bidder has bids to claim");
+
+                    // The allocation still goes to the bidder; only the refund is forfeited
+                    for payment in &mut result.payments {
+                        match forfeited
+                            .iter_mut()
+                            .find(|(asset, _)| *asset == payment.payment_asset)
+                        {
+                            Some((_, amount)) => amount.saturating_add_assign(payment.refund),
+                            None => forfeited.push((payment.payment_asset, payment.refund)),
+                        }
+                        payment.refund = Amount::ZERO;
+                    }
+
+                    self.runtime
+                        .prepare_message(AuctionMessage::SettlementResult { auction_id, result })
+                        .send_to(user_chain);
+                }
+            }
+        }
+
+        if let UnclaimedPolicy::ForfeitToTreasury(treasury) = policy {
+            let escrow_owner = self.runtime.application_id().into();
+            for (payment_asset, amount) in &forfeited {
+                if *amount > Amount::ZERO {
+                    self.transfer_asset(*payment_asset, escrow_owner, *amount, treasury)
+                        .expect("Failed to forfeit refund to treasury");
+                }
+            }
+        }
+
+        let swept = cursor + page.len();
+        let complete = swept >= positions.len();
+        let auction = self.state.auctions.get_mut(&auction_id).await.unwrap().unwrap();
+        auction.sweep_cursor = swept as u64;
+        auction.unclaimed_swept = complete;
+
+        let event = AuctionEvent::UnclaimedSwept {
+            auction_id,
+            bidders: bidders_swept,
+            forfeited,
+            complete,
+        };
+        self.runtime.emit(AUCTION_STREAM.into(), &event);
+    }
+
+    /// Position keys (user_chain, bidder) for an auction, in first-bid order
+    async fn bidder_positions(&mut self, auction_id: u64) -> Vec<(ChainId, AccountOwner)> {
+        self.state
+            .bidder_positions
+            .get(&auction_id)
+            .await
+            .unwrap()
+            .unwrap_or_default()
+    }
+
+    /// Whether a position exists and has not been claimed
+    async fn is_unclaimed(&mut self, auction_id: u64, user_chain: ChainId, bidder: AccountOwner) -> bool {
+        self.state
+            .positions
+            .get(&(user_chain, auction_id, bidder))
+            .await
+            .unwrap()
+            .is_some_and(|position| !position.claimed)
+    }
+
+    /// Push settlements for one auto-distributing auction, up to `budget` bidder positions
+    /// Continues from the auction's distribution cursor; returns the number of positions processed.
+    async fn distribute_settlements(&mut self, auction_id: u64, budget: usize) -> usize {
+        let auction = self.state.auctions.get(&auction_id).await.unwrap().unwrap();
+        let cursor = auction.distribution_cursor as usize;
+
+        // Positions in first-bid order (stable: no bids arrive after settlement)
+        let positions = self.bidder_positions(auction_id).await;
+
+        let page: Vec<(ChainId, AccountOwner)> =
+            positions.iter().skip(cursor).take(budget).copied().collect();
+        for (user_chain, bidder) in &page {
+            // Already-claimed positions are skipped by settle_claim
+            self.settle_claim(&auction, auction_id, *user_chain, *bidder, *user_chain)
+                .await;
+        }
+
+        let distributed = cursor + page.len();
+        let complete = distributed >= positions.len();
+        let auction = self.state.auctions.get_mut(&auction_id).await.unwrap().unwrap();
+        auction.distribution_cursor = distributed as u64;
+        auction.distribution_complete = complete;
+        if complete {
+            self.state.pending_distributions.remove(&auction_id).unwrap();
+        }
+
+        let event = AuctionEvent::SettlementsDistributed {
+            auction_id,
+            distributed: distributed as u64,
+            total: positions.len() as u64,
+        };
+        self.runtime.emit(AUCTION_STREAM.into(), &event);
+
+        page.len()
+    }
+
+    /// Claim every settled auction in `auction_ids` for the signer in one pass
+    /// Auctions that are unknown, unsettled or have nothing to claim are skipped.
+    /// Refunds are summed per asset (and delivery) into one transfer each, and the
+    /// results go back to the UIC in a single SettlementResults message.
+    async fn handle_claim_all(&mut self, auction_ids: Vec<u64>, user_chain: ChainId) {
+        let bidder = self
+            .runtime
+            .authenticated_signer()
+            .expect("ClaimAll must be authenticated");
+
+        self.assert_migrated().await;
+
+        let mut results = Vec::new();
+        let mut refunds: Vec<(AccountOwner, SettlementDelivery, PaymentAsset, Amount)> = Vec::new();
+
+        for auction_id in auction_ids {
+            let Some(auction) = self.state.auctions.get(&auction_id).await.unwrap() else {
+                continue;
+            };
+            if auction.status != shared::types::AuctionStatus::Settled {
+                continue;
+            }
+            let mut claimed: Option<SettlementResult> = None;
+            for (position_chain, owner) in
+                self.claimable_positions(bidder, auction_id, user_chain).await
+            {
+                let Some((result, delivery)) = self
+                    .claim_bids(&auction, auction_id, position_chain, owner)
+                    .await
+                else {
+                    continue;
+                };
+
+                for payment in &result.payments {
+                    match refunds.iter_mut().find(|(refund_owner, refund_delivery, asset, _)| {
+                        *refund_owner == owner
+                            && *refund_delivery == delivery
+                            && *asset == payment.payment_asset
+                    }) {
+                        Some((_, _, _, amount)) => amount.saturating_add_assign(payment.refund),
+                        None => {
+                            refunds.push((owner, delivery, payment.payment_asset, payment.refund))
+                        }
+                    }
+                }
+                match claimed.as_mut() {
+                    Some(settlement) => merge_settlement(settlement, result),
+                    None => claimed = Some(result),
+                }
+            }
+            if let Some(result) = claimed {
+                results.push((auction_id, result));
+            }
+
+            // Gifts arrive as their own SettlementResult, one per payer
+            let recipient = Account {
+                chain_id: user_chain,
+                owner: bidder,
+            };
+            self.claim_gifts(&auction, auction_id, recipient).await;
+        }
+
+        if results.is_empty() {
+            return;
+        }
+
+        for (owner, delivery, payment_asset, amount) in &refunds {
+            if *amount == Amount::ZERO {
+                continue;
+            }
+            match self.refund_account(*owner, user_chain, *delivery) {
+                Some(account) => {
+                    let escrow_owner = self.runtime.application_id().into();
+                    self.transfer_asset(*payment_asset, escrow_owner, *amount, account)
+                        .expect("Failed to refund payment to user");
+                }
+                None => self.credit_deposit(*owner, *payment_asset, *amount).await,
+            }
+        }
+
+        let event = AuctionEvent::AllSettlementsClaimed {
+            user_chain,
+            bidder,
+            auction_ids: results.iter().map(|(auction_id, _)| *auction_id).collect(),
+            refunds: refunds
+                .into_iter()
+                .map(|(_, _, payment_asset, amount)| (payment_asset, amount))
+                .collect(),
+        };
+        self.runtime.emit(AUCTION_STREAM.into(), &event);
+
+        self.runtime
+            .prepare_message(AuctionMessage::SettlementResults { results })
+            .send_to(user_chain);
+    }
+
+    /// Mark the bidder's unclaimed bids for a settled auction as claimed and notify gift recipients
+    /// Returns the bidder's settlement and refund delivery, or None if there is nothing to claim.
+    /// Refunds are left to the caller.
+    async fn claim_bids(
+        &mut self,
+        auction: &AuctionData,
+        auction_id: u64,
+        user_chain: ChainId,
+        bidder: AccountOwner,
+    ) -> Option<(SettlementResult, SettlementDelivery)> {
+        let clearing_price = auction.clearing_price.expect("Clearing price not set");
+
+        // O(1) lookup: only the bidder's aggregate position is read
+        let key = (user_chain, auction_id, bidder);
+        let mut position = self.state.positions.get(&key).await.unwrap()?;
+        if position.claimed {
+            return None;
+        }
+
+        let payments = asset_settlements(&auction.params, clearing_price, &position.paid);
+        let total_quantity = position.quantity;
+        let total_cost = clearing_price.saturating_mul(total_quantity as u128);
+
+        // Units the bidder paid for on behalf of gift recipients
+        let gifted_quantity: u64 = position.gifts.iter().map(|gift| gift.quantity).sum();
+
+        // Recipients that already claimed their gift have had their allocation
+        let undelivered: Vec<GiftedQuantity> = position
+            .gifts
+            .iter()
+            .filter(|gift| !gift.delivered)
+            .copied()
+            .collect();
+        for gift in &mut position.gifts {
+            gift.delivered = true;
+        }
+
+        // The bidder's most recent bid's delivery preference decides where the refund goes
+        let delivery = position.delivery;
+
+        position.claimed = true;
+        self.state.positions.insert(&key, position).unwrap();
+
+        // Send each remaining gift recipient their allocation
+        let payer = Account {
+            chain_id: user_chain,
+            owner: bidder,
+        };
+        for GiftedQuantity {
+            recipient,
+            quantity,
+            ..
+        } in undelivered
+        {
+            self.send_gift(auction_id, clearing_price, payer, recipient, quantity);
+        }
+
+        // Emit settlement claimed event
+        let event = AuctionEvent::SettlementClaimed {
+            auction_id,
+            user_chain,
+            allocated_quantity: total_quantity,
+            clearing_price,
+            total_cost,
+            payments: payments.clone(),
+        };
+        self.runtime.emit(AUCTION_STREAM.into(), &event);
+
+        let result = SettlementResult {
+            allocated_quantity: total_quantity - gifted_quantity,
+            clearing_price,
+            total_cost,
+            payments,
+            gifted_quantity,
+            gifted_from: None,
+        };
+        Some((result, delivery))
+    }
+
+    /// Deliver the gifts others paid for to `recipient`, ahead of the payers' own claims
+    /// Returns false if there was nothing left to deliver
+    async fn claim_gifts(
+        &mut self,
+        auction: &AuctionData,
+        auction_id: u64,
+        recipient: Account,
+    ) -> bool {
+        let clearing_price = auction.clearing_price.expect("Clearing price not set");
+        let payers = self
+            .state
+            .gift_positions
+            .get(&(recipient, auction_id))
+            .await
+            .unwrap()
+            .unwrap_or_default();
+
+        let mut delivered = false;
+        for (payer_chain, payer) in payers {
+            let key = (payer_chain, auction_id, payer);
+            let Some(mut position) = self.state.positions.get(&key).await.unwrap() else {
+                continue;
+            };
+            let Some(gift) = position
+                .gifts
+                .iter_mut()
+                .find(|gift| gift.recipient == recipient && !gift.delivered)
+            else {
+                continue;
+            };
+            gift.delivered = true;
+            let quantity = gift.quantity;
+            self.state.positions.insert(&key, position).unwrap();
+
+            let payer = Account {
+                chain_id: payer_chain,
+                owner: payer,
+            };
+            self.send_gift(auction_id, clearing_price, payer, recipient, quantity);
+            delivered = true;
+        }
+
+        delivered
+    }
+
+    /// Drop a position from the gift index of each recipient it paid for
+    async fn unindex_gifts(&mut self, key: (ChainId, u64, AccountOwner)) {
+        let (user_chain, auction_id, bidder) = key;
+        let Some(position) = self.state.positions.get(&key).await.unwrap() else {
+            return;
+        };
+        for gift in position.gifts {
+            let index_key = (gift.recipient, auction_id);
+            let mut payers = self
+                .state
+                .gift_positions
+                .get(&index_key)
+                .await
+                .unwrap()
+                .unwrap_or_default();
+            payers.retain(|payer| *payer != (user_chain, bidder));
+            if payers.is_empty() {
+                self.state.gift_positions.remove(&index_key).unwrap();
+            } else {
+                self.state.gift_positions.insert(&index_key, payers).unwrap();
+            }
+        }
+    }
+
+    /// Send a gift recipient the allocation `payer` bought for it
+    fn send_gift(
+        &mut self,
+        auction_id: u64,
+        clearing_price: Amount,
+        payer: Account,
+        recipient: Account,
+        quantity: u64,
+    ) {
+        self.runtime
+            .prepare_message(AuctionMessage::SettlementResult {
+                auction_id,
+                result: SettlementResult {
+                    allocated_quantity: quantity,
+                    clearing_price,
+                    total_cost: Amount::ZERO, // Paid by the gifting bidder
+                    payments: Vec::new(),
+                    gifted_quantity: 0,
+                    gifted_from: Some(payer),
+                },
+            })
+            .send_to(recipient.chain_id);
+    }
+
+    // ═══════════════════════════════════════════════════════════
+    // Basket Handlers (AAC only)
+    // ═══════════════════════════════════════════════════════════
+
+    /// Buy every leg of a basket or none
+    /// All legs are validated before any funds move; payments are then collected
+    /// leg by leg and returned if one fails, so a rejected basket charges nothing.
+    async fn handle_buy_basket(
+        &mut self,
+        user_chain: ChainId,
+        legs: Vec<BasketLeg>,
+        delivery: SettlementDelivery,
+        payment: PaymentSource,
+    ) {
+        self.assert_migrated().await;
+
+        let basket_id = *self.state.next_basket_id.get();
+        self.state.next_basket_id.set(basket_id + 1);
+
+        let now = self.runtime.system_time();
+        let bidder = self.bid_owner(payment);
+
+        // Phase 1: validate every leg
+        let mut quotes = Vec::with_capacity(legs.len());
+        for (index, leg) in legs.iter().enumerate() {
+            let duplicate = legs[..index]
+                .iter()
+                .any(|earlier| earlier.auction_id == leg.auction_id);
+            let quote = if duplicate {
+                let event = AuctionEvent::BidRejected {
+                    auction_id: leg.auction_id,
+                    user_chain,
+                    reason: "Auction appears in more than one basket leg".to_string(),
+                };
+                self.runtime.emit(AUCTION_STREAM.into(), &event);
+                Err(())
+            } else {
+                self.quote_basket_leg(leg, user_chain, now).await
+            };
+
+            match quote {
+                Ok(quote) => quotes.push(quote),
+                Err(()) => {
+                    self.reject_basket(basket_id, user_chain, leg.auction_id);
+                    return;
+                }
+            }
+        }
+
+        // Phase 2: collect payment for every leg, returning what was taken if one fails
+        let mut collected = Vec::with_capacity(legs.len());
+        for (leg, (_, amount_due, payment_asset, _)) in legs.iter().zip(&quotes) {
+            match self
+                .take_payment(
+                    leg.auction_id,
+                    user_chain,
+                    bidder,
+                    *amount_due,
+                    payment,
+                    *payment_asset,
+                )
+                .await
+            {
+                Ok(amount) => collected.push((*payment_asset, amount)),
+                Err(()) => {
+                    for (payment_asset, amount) in collected {
+                        self.return_basket_payment(bidder, payment, payment_asset, amount)
+                            .await;
+                    }
+                    self.reject_basket(basket_id, user_chain, leg.auction_id);
+                    return;
+                }
+            }
+        }
+
+        // Phase 3: record every leg
+        let mut fills = Vec::with_capacity(legs.len());
+        for ((leg, (price, _, payment_asset, new_status)), (_, amount_paid)) in
+            legs.iter().zip(quotes).zip(collected)
+        {
+            if let Some(status) = new_status {
+                let auction = self.state.auctions.get_mut(&leg.auction_id).await.unwrap().unwrap();
+                auction.status = status;
+            }
+
+            let bid_id = self
+                .record_accepted_bid(
+                    leg.auction_id,
+                    user_chain,
+                    bidder,
+                    leg.quantity,
+                    leg.quantity,
+                    amount_paid,
+                    price,
+                    delivery,
+                    payment_asset,
+                    None,
+                )
+                .await;
+
+            fills.push(BasketLegFill {
+                auction_id: leg.auction_id,
+                bid_id,
+                quantity: leg.quantity,
+                amount_paid,
+            });
+        }
+
+        let event = AuctionEvent::BasketAccepted {
+            basket_id,
+            user_chain,
+            bidder,
+            legs: fills,
+            timestamp: now,
+        };
+        self.runtime.emit(AUCTION_STREAM.into(), &event);
+    }
+
+    /// Check one basket leg without moving funds
+    /// Returns Ok((price_per_unit, amount_due, payment_asset, status_transition)), Err if the leg is rejected
+    async fn quote_basket_leg(
+        &mut self,
+        leg: &BasketLeg,
+        user_chain: ChainId,
+        now: Timestamp,
+    ) -> Result<(Amount, Amount, PaymentAsset, Option<shared::types::AuctionStatus>), ()> {
+        let auction_id = leg.auction_id;
+        let Some(auction) = self.state.auctions.get(&auction_id).await.unwrap() else {
+            let event = AuctionEvent::BidRejected {
+                auction_id,
+                user_chain,
+                reason: "Auction not found".to_string(),
+            };
+            self.runtime.emit(AUCTION_STREAM.into(), &event);
+            return Err(());
+        };
+
+        // Fairness-mode bids are queued, so they cannot be filled atomically
+        if auction.params.fair_ordering_window.is_some() {
+            let event = AuctionEvent::BidRejected {
+                auction_id,
+                user_chain,
+                reason: "Fairness-mode auctions cannot be bought in a basket".to_string(),
+            };
+            self.runtime.emit(AUCTION_STREAM.into(), &event);
+            return Err(());
+        }
+
+        let current_price = self.calculate_current_price(auction_id).await;
+
+        if auction.status == shared::types::AuctionStatus::Active {
+            if let Some(clear_reason) = self.end_reason(&auction, current_price, now) {
+                self.end_auction(auction_id, current_price, clear_reason).await;
+
+                let event = AuctionEvent::BidRejected {
+                    auction_id,
+                    user_chain,
+                    reason: format!("Auction ended: {:?}", clear_reason),
+                };
+                self.runtime.emit(AUCTION_STREAM.into(), &event);
+                return Err(());
+            }
+        }
+
+        let new_status = self.validate_auction_state(
+            auction.status,
+            auction.params.start_time,
+            auction.params.end_time,
+            now,
+            auction_id,
+            user_chain,
+        )?;
+
+        self.validate_bid_limits(
+            current_price,
+            Some(leg.max_price_per_unit),
+            None,
+            now,
+            auction_id,
+            user_chain,
+        )?;
+
+        self.validate_supply(
+            auction.total_supply,
+            auction.sold,
+            leg.quantity,
+            FillPolicy::AllOrNothing,
+            auction_id,
+            user_chain,
+        )?;
+
+        let amount_due = current_price.saturating_mul(leg.quantity as u128);
+        Ok((current_price, amount_due, auction.params.payment_asset, new_status))
+    }
+
+    /// Return a basket leg's payment to where it was taken from
+    async fn return_basket_payment(
+        &mut self,
+        bidder: AccountOwner,
+        payment: PaymentSource,
+        payment_asset: PaymentAsset,
+        amount: Amount,
+    ) {
+        match payment {
+            PaymentSource::Deposit => self.credit_deposit(bidder, payment_asset, amount).await,
+            _ => {
+                let escrow_owner = self.runtime.application_id().into();
+                let bidder_account = Account {
+                    chain_id: self.runtime.chain_id(),
+                    owner: bidder,
+                };
+                self.transfer_asset(payment_asset, escrow_owner, amount, bidder_account)
+                    .expect("Failed to return basket payment");
+            }
+        }
+    }
+
+    /// Emit the rejection for a basket (`auction_id` is the leg that failed)
+    fn reject_basket(&mut self, basket_id: u64, user_chain: ChainId, auction_id: u64) {
+        let event = AuctionEvent::BasketRejected {
+            basket_id,
+            user_chain,
+            auction_id,
+        };
+        self.runtime.emit(AUCTION_STREAM.into(), &event);
+    }
+
+    // ═══════════════════════════════════════════════════════════
+    // Pool Handlers (AAC only)
+    // ═══════════════════════════════════════════════════════════
+
+    /// Open a pooled group bid, returning its ID
+    async fn handle_open_pool(
+        &mut self,
+        organiser: AccountOwner,
+        auction_id: u64,
+        target_quantity: u64,
+        max_price_per_unit: Amount,
+        payment_asset: Option<PaymentAsset>,
+    ) -> u64 {
+        let auction = self
+            .state
+            .auctions
+            .get(&auction_id)
+            .await
+            .unwrap()
+            .expect("Auction not found");
+
+        assert!(
+            matches!(
+                auction.status,
+                shared::types::AuctionStatus::Scheduled | shared::types::AuctionStatus::Active
+            ),
+            "Pools can only be opened for scheduled or active auctions"
+        );
+        assert!(
+            auction.params.fair_ordering_window.is_none(),
+            "Pools are not supported in fairness mode"
+        );
+        assert!(target_quantity > 0, "Pool target quantity must be positive");
+
+        let payment_asset = payment_asset.unwrap_or(auction.params.payment_asset);
+        assert!(
+            auction.params.rate_for(payment_asset).is_some(),
+            "Payment asset not accepted by this auction"
+        );
+
+        let pool_id = *self.state.next_pool_id.get();
+        self.state.next_pool_id.set(pool_id + 1);
+
+        let pool = PoolData {
+            pool_id,
+            auction_id,
+            organiser,
+            target_quantity,
+            max_price_per_unit,
+            payment_asset,
+            status: PoolStatus::Open,
+            total_contributed: Amount::ZERO,
+            contributions: Vec::new(),
+            bid_id: None,
+            allocated_quantity: 0,
+            shares_computed: false,
+        };
+        self.state.pools.insert(&pool_id, pool).unwrap();
+
+        let event = AuctionEvent::PoolOpened {
+            pool_id,
+            auction_id,
+            organiser,
+            target_quantity,
+        };
+        self.runtime.emit(AUCTION_STREAM.into(), &event);
+
+        pool_id
+    }
+
+    /// Record a contribution whose funds are already in escrow
+    /// Returns Err if the pool cannot accept it (the caller returns the funds)
+    async fn handle_contribute_to_pool(
+        &mut self,
+        contributor: AccountOwner,
+        chain_id: ChainId,
+        pool_id: u64,
+        payment_asset: PaymentAsset,
+        amount: Amount,
+    ) -> Result<(), String> {
+        let Some(pool) = self.state.pools.get_mut(&pool_id).await.unwrap() else {
+            return Err("Pool not found".to_string());
+        };
+        if pool.status != PoolStatus::Open {
+            return Err("Pool is not open".to_string());
+        }
+        if pool.payment_asset != payment_asset {
+            return Err("Contribution is not in the pool's payment asset".to_string());
+        }
+
+        match pool
+            .contributions
+            .iter_mut()
+            .find(|contribution| contribution.contributor == contributor)
+        {
+            Some(contribution) => {
+                contribution.amount.saturating_add_assign(amount);
+                contribution.chain_id = chain_id;
+            }
+            None => pool.contributions.push(PoolContribution {
+                contributor,
+                chain_id,
+                amount,
+                allocated_quantity: 0,
+                refund: Amount::ZERO,
+                claimed: false,
+            }),
+        }
+        pool.total_contributed.saturating_add_assign(amount);
+        let total_contributed = pool.total_contributed;
+
+        let event = AuctionEvent::PoolContributed {
+            pool_id,
+            contributor,
+            amount,
+            total_contributed,
+        };
+        self.runtime.emit(AUCTION_STREAM.into(), &event);
+
+        Ok(())
+    }
+
+    /// Place the pool's combined bid with the pooled escrow
+    async fn handle_place_pool_bid(&mut self, organiser: AccountOwner, pool_id: u64) {
+        let pool = self.state.pools.get(&pool_id).await.unwrap().expect("Pool not found");
+        assert_eq!(pool.organiser, organiser, "Only the organiser can place the pool's bid");
+        assert_eq!(pool.status, PoolStatus::Open, "Pool is not open");
+        assert!(pool.total_contributed > Amount::ZERO, "Pool has no contributions");
+
+        // Queued bids are filled later without the pool's bookkeeping
+        let auction = self
+            .state
+            .auctions
+            .get(&pool.auction_id)
+            .await
+            .unwrap()
+            .expect("Auction not found");
+        assert!(
+            auction.params.fair_ordering_window.is_none(),
+            "Pools cannot bid on fairness-mode auctions"
+        );
+
+        let aac_chain = self.runtime.chain_id();
+        let placed = self
+            .handle_place_bid(
+                pool.auction_id,
+                aac_chain,
+                pool.target_quantity,
+                Some(pool.max_price_per_unit),
+                None,
+                FillPolicy::Partial,
+                self.runtime.system_time(),
+                SettlementDelivery::Aac,
+                PaymentSource::Pool { pool_id },
+                Some(pool.payment_asset),
+                None,
+            )
+            .await;
+
+        // Rejected: the pool stays open and keeps its funds
+        let Ok(Some(bid_id)) = placed else {
+            return;
+        };
+
+        // Each pool bids under its own owner, so the position holds exactly this bid.
+        // The pool settles it itself (pro rata), so the position is marked claimed
+        // for the regular claim path
+        let bidder = pool_bidder(self.runtime.application_id().forget_abi(), pool_id);
+        let key = (aac_chain, pool.auction_id, bidder);
+        let position = self.state.positions.get_mut(&key).await.unwrap().expect("Pool position");
+        position.claimed = true;
+        let quantity = position.quantity;
+
+        let pool = self.state.pools.get_mut(&pool_id).await.unwrap().unwrap();
+        pool.status = PoolStatus::BidPlaced;
+        pool.bid_id = Some(bid_id);
+        pool.allocated_quantity = quantity;
+        let auction_id = pool.auction_id;
+
+        let event = AuctionEvent::PoolBidPlaced {
+            pool_id,
+            auction_id,
+            bid_id,
+            quantity,
+        };
+        self.runtime.emit(AUCTION_STREAM.into(), &event);
+    }
+
+    /// Cancel an open pool; contributors reclaim their funds with ClaimPoolShare
+    async fn handle_cancel_pool(&mut self, organiser: AccountOwner, pool_id: u64) {
+        let pool = self.state.pools.get_mut(&pool_id).await.unwrap().expect("Pool not found");
+        assert_eq!(pool.organiser, organiser, "Only the organiser can cancel the pool");
+        assert_eq!(pool.status, PoolStatus::Open, "Only open pools can be cancelled");
+
+        pool.status = PoolStatus::Cancelled;
+
+        let event = AuctionEvent::PoolCancelled { pool_id };
+        self.runtime.emit(AUCTION_STREAM.into(), &event);
+    }
+
+    /// Pay out a contributor's share of a pool
+    /// - Bid placed and auction settled: pro rata share of the allocation and refund
+    /// - Pool cancelled, or auction over without a pool bid: full contribution back
+    async fn handle_claim_pool_share(&mut self, contributor: AccountOwner, pool_id: u64) {
+        let mut pool = self.state.pools.get(&pool_id).await.unwrap().expect("Pool not found");
+        let auction = self
+            .state
+            .auctions
+            .get(&pool.auction_id)
+            .await
+            .unwrap()
+            .expect("Auction not found");
+
+        let clearing_price = match pool.status {
+            PoolStatus::BidPlaced => {
+                assert_eq!(
+                    auction.status,
+                    shared::types::AuctionStatus::Settled,
+                    "Auction not settled yet"
+                );
+                auction.clearing_price.expect("Clearing price not set")
+            }
+            PoolStatus::Cancelled => Amount::ZERO,
+            PoolStatus::Open => {
+                assert!(
+                    !matches!(
+                        auction.status,
+                        shared::types::AuctionStatus::Scheduled | shared::types::AuctionStatus::Active
+                    ),
+                    "Pool is still open for an active auction"
+                );
+                Amount::ZERO
+            }
+        };
+
+        // Fix everyone's shares on the first claim: allocation and refund are both pro rata
+        if !pool.shares_computed {
+            let weights: Vec<Amount> = pool
+                .contributions
+                .iter()
+                .map(|contribution| contribution.amount)
+                .collect();
+            let (units, refund_total) = if pool.status == PoolStatus::BidPlaced {
+                let rate = auction
+                    .params
+                    .rate_for(pool.payment_asset)
+                    .expect("Pool paid in an accepted asset");
+                let cost = convert_to_asset(
+                    clearing_price.saturating_mul(pool.allocated_quantity as u128),
+                    rate,
+                );
+                (
+                    pro_rata_units(pool.allocated_quantity, &weights),
+                    pool.total_contributed.saturating_sub(cost),
+                )
+            } else {
+                (vec![0; weights.len()], pool.total_contributed)
+            };
 
-        // Mark all bids as claimed
-        for bid in &mut user_bids {
-            if !bid.claimed {
-                bid.claimed = true;
+            for (contribution, allocated_quantity) in pool.contributions.iter_mut().zip(units) {
+                contribution.allocated_quantity = allocated_quantity;
+                contribution.refund =
+                    pro_rata_amount(refund_total, contribution.amount, pool.total_contributed);
             }
+            pool.shares_computed = true;
         }
 
-        // Save updated bids
-        self.state
-            .user_auction_bids
-            .insert(&(user_chain, auction_id), user_bids)
-            .unwrap();
-
-        // Execute refund transfer (synchronous - on AAC)
-        self.refund_payment(auction_id, bidder, refund, payment_token_app);
+        let contribution = pool
+            .contributions
+            .iter_mut()
+            .find(|contribution| contribution.contributor == contributor)
+            .expect("No contribution to this pool");
+        assert!(!contribution.claimed, "Pool share already claimed");
+        contribution.claimed = true;
+
+        let share = contribution.clone();
+        let payment_asset = pool.payment_asset;
+        let auction_id = pool.auction_id;
+        self.state.pools.insert(&pool_id, pool).unwrap();
+
+        // Refund to the chain the contributor paid from
+        let refund_account = Account {
+            chain_id: share.chain_id,
+            owner: contributor,
+        };
+        self.refund_payment(auction_id, refund_account, share.refund, payment_asset);
 
-        // Send settlement result to user
+        let paid_for = share.amount.saturating_sub(share.refund);
         self.runtime
-            .prepare_message(AuctionMessage::SettlementResult {
+            .prepare_message(AuctionMessage::PoolShareSettled {
+                pool_id,
                 auction_id,
                 result: SettlementResult {
-                    allocated_quantity: total_quantity,
+                    allocated_quantity: share.allocated_quantity,
                     clearing_price,
-                    total_cost,
-                    refund,
+                    total_cost: clearing_price.saturating_mul(share.allocated_quantity as u128),
+                    payments: vec![AssetSettlement {
+                        payment_asset,
+                        quantity: share.allocated_quantity,
+                        paid: share.amount,
+                        cost: paid_for,
+                        refund: share.refund,
+                    }],
+                    gifted_quantity: 0,
+                    gifted_from: None,
                 },
             })
-            .send_to(user_chain);
+            .send_to(share.chain_id);
 
-        // Emit settlement claimed event
-        let event = AuctionEvent::SettlementClaimed {
-            auction_id,
-            user_chain,
-            allocated_quantity: total_quantity,
-            clearing_price,
-            total_cost,
-            refund,
+        let event = AuctionEvent::PoolShareClaimed {
+            pool_id,
+            contributor,
+            allocated_quantity: share.allocated_quantity,
+            refund: share.refund,
         };
         self.runtime.emit(AUCTION_STREAM.into(), &event);
     }
@@ -539,6 +2592,13 @@ impl AuctionContract {
     /// Calculate current price based on elapsed time since auction start
     /// On-demand calculation - no need to store or update price
     async fn calculate_current_price(&mut self, auction_id: u64) -> Amount {
+        let current_time = self.runtime.system_time();
+        self.calculate_price_at(auction_id, current_time).await
+    }
+
+    /// Calculate the auction price at an arbitrary point in time
+    /// Block-height clocked auctions always use the current AAC block height
+    async fn calculate_price_at(&mut self, auction_id: u64, current_time: Timestamp) -> Amount {
         let auction = self
             .state
             .auctions
@@ -547,63 +2607,109 @@ impl AuctionContract {
             .expect("Failed to get auction")
             .expect("Auction not found");
 
-        let current_time = self.runtime.system_time();
-
-        // Use shared utility function
-        shared::calculate_current_price(
-            auction.params.start_price,
-            auction.params.floor_price,
-            auction.params.price_decay_amount,
-            auction.params.price_decay_interval,
-            auction.params.start_time,
-            current_time,
-        )
+        // Use shared utility functions
+        match auction.params.price_clock {
+            PriceClock::WallClock => shared::calculate_current_price(
+                auction.params.start_price,
+                auction.params.floor_price,
+                auction.params.price_decay_amount,
+                auction.params.price_decay_interval,
+                auction.params.start_time,
+                current_time,
+            ),
+            PriceClock::BlockHeight { start_height } => shared::calculate_price_at_height(
+                auction.params.start_price,
+                auction.params.floor_price,
+                auction.params.price_decay_amount,
+                auction.params.price_decay_interval,
+                start_height,
+                self.runtime.block_height(),
+            ),
+        }
     }
 
     /// Handle bid placement from user chains
     /// Main bid processing orchestrator - delegates to helper methods for testability
-    async fn handle_place_bid(&mut self, auction_id: u64, user_chain: ChainId, quantity: u64) {
+    /// Returns the recorded bid's ID, None if the bid was queued (fairness mode),
+    /// Err if it was rejected
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_place_bid(
+        &mut self,
+        auction_id: u64,
+        user_chain: ChainId,
+        quantity: u64,
+        max_price_per_unit: Option<Amount>,
+        deadline: Option<Timestamp>,
+        fill_policy: FillPolicy,
+        submitted_at: Timestamp,
+        delivery: SettlementDelivery,
+        payment: PaymentSource,
+        payment_asset: Option<PaymentAsset>,
+        recipient: Option<Account>,
+    ) -> Result<Option<u64>, ()> {
+        // Positions are incomplete until every legacy bid has been migrated
+        if !self.is_migrated().await {
+            let event = AuctionEvent::BidRejected {
+                auction_id,
+                user_chain,
+                reason: "State migration in progress".to_string(),
+            };
+            self.runtime.emit(AUCTION_STREAM.into(), &event);
+            return Err(());
+        }
+
         let current_price = self.calculate_current_price(auction_id).await;
         let now = self.runtime.system_time();
 
         // Get auction and extract needed data
-        let auction = self.state.auctions.get_mut(&auction_id).await.unwrap().unwrap();
+        let auction = self.state.auctions.get(&auction_id).await.unwrap().unwrap();
         let current_status = auction.status;
         let start_time = auction.params.start_time;
         let end_time = auction.params.end_time;
         let total_supply = auction.total_supply;
         let sold = auction.sold;
-        let payment_token_app = auction.params.payment_token_app;
-
-        // Handle time expiration (special case requiring async settlement)
-        if now > end_time && current_status == shared::types::AuctionStatus::Active {
-            auction.clearing_price = Some(current_price);
-            auction.status = shared::types::AuctionStatus::Ended;
-            let total_bids = auction.total_bids;
+        let payment_asset = payment_asset.unwrap_or(auction.params.payment_asset);
+        let fair_ordering_window = auction.params.fair_ordering_window;
 
-            let event = AuctionEvent::AuctionCleared {
-                auction_id,
-                clearing_price: current_price,
-                total_bids,
-                reason: ClearReason::TimeExpired,
-            };
-            self.runtime.emit(AUCTION_STREAM.into(), &event);
+        // The bid's asset must be accepted by the auction (and match any escrowed funds)
+        let escrow_matches = match payment {
+            PaymentSource::Escrowed { payment_asset: escrowed_asset, .. } => {
+                escrowed_asset == payment_asset
+            }
+            _ => true,
+        };
+        let rate = match auction.params.rate_for(payment_asset) {
+            Some(rate) if escrow_matches => rate,
+            _ => {
+                let event = AuctionEvent::BidRejected {
+                    auction_id,
+                    user_chain,
+                    reason: format!("Payment asset {:?} not accepted by this auction", payment_asset),
+                };
+                self.runtime.emit(AUCTION_STREAM.into(), &event);
+                return Err(());
+            }
+        };
 
-            let _ = auction;
-            self.settle_auction(auction_id).await;
+        // Handle end conditions (special case requiring async settlement)
+        if current_status == shared::types::AuctionStatus::Active {
+            if let Some(clear_reason) = self.end_reason(&auction, current_price, now) {
+                self.end_auction(auction_id, current_price, clear_reason).await;
 
-            let event = AuctionEvent::BidRejected {
-                auction_id,
-                user_chain,
-                reason: format!("Auction expired at: {:?}", end_time),
-            };
-            self.runtime.emit(AUCTION_STREAM.into(), &event);
-            return;
+                let reason = match clear_reason {
+                    ClearReason::TimeExpired => format!("Auction expired at: {:?}", end_time),
+                    other => format!("Auction ended: {:?}", other),
+                };
+                let event = AuctionEvent::BidRejected {
+                    auction_id,
+                    user_chain,
+                    reason,
+                };
+                self.runtime.emit(AUCTION_STREAM.into(), &event);
+                return Err(());
+            }
         }
 
-        // Drop auction reference before calling helper methods
-        let _ = auction;
-
         // Validate auction state (Scheduled→Active transition, active check)
         let new_status = match self.validate_auction_state(
             current_status,
@@ -614,7 +2720,7 @@ impl AuctionContract {
             user_chain,
         ) {
             Ok(status) => status,
-            Err(()) => return,
+            Err(()) => return Err(()),
         };
 
         // Apply status change if needed
@@ -623,51 +2729,527 @@ impl AuctionContract {
             auction.status = status;
         }
 
+        // Fairness mode prices the bid at the UIC's submission time
+        let price_per_unit = match fair_ordering_window {
+            Some(window) => {
+                // A future submission time would price the bid further down the curve
+                let reason = if submitted_at > now {
+                    Some(format!(
+                        "Submission time {:?} is later than the AAC time {:?}",
+                        submitted_at, now
+                    ))
+                } else if now.delta_since(submitted_at).as_micros() > window {
+                    Some(format!(
+                        "Submission time {:?} outside fairness window of {} microseconds",
+                        submitted_at, window
+                    ))
+                } else {
+                    None
+                };
+
+                if let Some(reason) = reason {
+                    let event = AuctionEvent::BidRejected {
+                        auction_id,
+                        user_chain,
+                        reason,
+                    };
+                    self.runtime.emit(AUCTION_STREAM.into(), &event);
+                    return Err(());
+                }
+
+                self.calculate_price_at(auction_id, submitted_at).await
+            }
+            None => current_price,
+        };
+
+        // Validate user's slippage limits (before any tokens are moved)
+        if self
+            .validate_bid_limits(
+                price_per_unit,
+                max_price_per_unit,
+                deadline,
+                now,
+                auction_id,
+                user_chain,
+            )
+            .is_err()
+        {
+            return Err(());
+        }
+
+        // Fairness mode: escrow the full requested quantity now and queue the bid.
+        // Units are allocated in submission-time order once the fairness window has passed.
+        if let Some(window) = fair_ordering_window {
+            let bidder = self.bid_owner(payment);
+
+            let amount_due = convert_to_asset(price_per_unit.saturating_mul(quantity as u128), rate);
+            let escrowed = match self.take_payment(
+                auction_id,
+                user_chain,
+                bidder,
+                amount_due,
+                payment,
+                payment_asset,
+            )
+            .await
+            {
+                Ok(amount) => amount,
+                Err(()) => return Err(()),
+            };
+
+            let mut pending = self
+                .state
+                .pending_bids
+                .get(&auction_id)
+                .await
+                .unwrap()
+                .unwrap_or_default();
+
+            pending.push(PendingBid {
+                user_chain,
+                bidder,
+                quantity,
+                fill_policy,
+                price_per_unit,
+                escrowed,
+                submitted_at,
+                delivery,
+                payment_asset,
+                recipient,
+            });
+
+            self.state.pending_bids.insert(&auction_id, pending).unwrap();
+
+            let horizon = Timestamp::from(now.micros().saturating_sub(window));
+            self.drain_pending_bids(auction_id, horizon).await;
+            return Ok(None);
+        }
+
         // Validate supply availability
         let accepted_quantity = match self.validate_supply(
             total_supply,
             sold,
             quantity,
+            fill_policy,
             auction_id,
             user_chain,
         ) {
             Ok(qty) => qty,
-            Err(()) => return,
+            Err(()) => return Err(()),
         };
 
-        // Calculate payment and get authenticated bidder
-        let amount_paid = current_price.saturating_mul(accepted_quantity as u128);
-        let bidder = self
-            .runtime
-            .authenticated_signer()
-            .expect("PlaceBid must be authenticated");
+        // Calculate payment (converted into the bid's asset) and get the paying owner
+        let amount_due = convert_to_asset(current_price.saturating_mul(accepted_quantity as u128), rate);
+        let bidder = self.bid_owner(payment);
 
         // Collect payment (fail-fast before state changes)
-        if let Err(reason) = self.collect_payment(bidder, amount_paid, payment_token_app) {
+        // Funds escrowed from the user chain are held in full; the excess is refunded at settlement
+        let amount_paid = match self.take_payment(
+            auction_id,
+            user_chain,
+            bidder,
+            amount_due,
+            payment,
+            payment_asset,
+        )
+        .await
+        {
+            Ok(amount) => amount,
+            Err(()) => return Err(()),
+        };
+
+        let bid_id = self
+            .record_accepted_bid(
+                auction_id,
+                user_chain,
+                bidder,
+                quantity,
+                accepted_quantity,
+                amount_paid,
+                current_price,
+                delivery,
+                payment_asset,
+                recipient,
+            )
+            .await;
+
+        Ok(Some(bid_id))
+    }
+
+    /// Owner a bid is placed for: the allowance owner for BuyFor, the pool's own owner
+    /// for pool bids, otherwise the signer
+    fn bid_owner(&mut self, payment: PaymentSource) -> AccountOwner {
+        match payment {
+            PaymentSource::Allowance { owner } => owner,
+            PaymentSource::Pool { pool_id } => {
+                pool_bidder(self.runtime.application_id().forget_abi(), pool_id)
+            }
+            _ => self
+                .runtime
+                .authenticated_signer()
+                .expect("PlaceBid must be authenticated"),
+        }
+    }
+
+    /// Take payment for a bid into escrow
+    /// Returns Ok(amount held in escrow for the bid), Err if payment fails
+    async fn take_payment(
+        &mut self,
+        auction_id: u64,
+        user_chain: ChainId,
+        bidder: AccountOwner,
+        amount_due: Amount,
+        payment: PaymentSource,
+        payment_asset: PaymentAsset,
+    ) -> Result<Amount, ()> {
+        let result = match payment {
+            PaymentSource::AacBalance => self
+                .collect_payment(bidder, amount_due, payment_asset)
+                .map(|()| amount_due)
+                .map_err(|reason| {
+                    format!(
+                        "Payment failed: {}. Ensure you have sufficient balance on AAC",
+                        reason
+                    )
+                }),
+            PaymentSource::Allowance { owner } => self
+                .collect_payment_from_allowance(owner, amount_due, payment_asset)
+                .map(|()| amount_due)
+                .map_err(|reason| {
+                    format!(
+                        "Payment failed: {}. The beneficiary must approve the auction app on AAC",
+                        reason
+                    )
+                }),
+            PaymentSource::Deposit => self
+                .debit_deposit(bidder, payment_asset, amount_due)
+                .await
+                .map(|()| amount_due)
+                .map_err(|reason| format!("Payment failed: {}. Deposit more funds first", reason)),
+            PaymentSource::Pool { pool_id } => {
+                let pool = self.state.pools.get(&pool_id).await.unwrap().expect("Pool not found");
+                if pool.total_contributed >= amount_due {
+                    Ok(pool.total_contributed)
+                } else {
+                    Err(format!(
+                        "Pool contributions do not cover the bid: contributed {}, required {}",
+                        pool.total_contributed, amount_due
+                    ))
+                }
+            }
+            PaymentSource::Escrowed { amount, .. } => {
+                if amount >= amount_due {
+                    Ok(amount)
+                } else {
+                    Err(format!(
+                        "Escrowed funds do not cover the bid: escrowed {}, required {}",
+                        amount, amount_due
+                    ))
+                }
+            }
+        };
+
+        result.map_err(|reason| {
             let event = AuctionEvent::BidRejected {
                 auction_id,
                 user_chain,
-                reason: format!(
-                    "Payment failed: {}. Ensure you have sufficient fungible token balance on AAC",
-                    reason
-                ),
+                reason,
             };
             self.runtime.emit(AUCTION_STREAM.into(), &event);
-            return;
-        }
+        })
+    }
 
+    /// Record a paid bid: store it, update counters, emit events and check settlement
+    /// Returns the new bid's ID
+    #[allow(clippy::too_many_arguments)]
+    async fn record_accepted_bid(
+        &mut self,
+        auction_id: u64,
+        user_chain: ChainId,
+        bidder: AccountOwner,
+        requested_quantity: u64,
+        accepted_quantity: u64,
+        amount_paid: Amount,
+        price_per_unit: Amount,
+        delivery: SettlementDelivery,
+        payment_asset: PaymentAsset,
+        recipient: Option<Account>,
+    ) -> u64 {
         // Create and record bid
         let (bid, is_first_bid) = self
-            .create_and_record_bid(auction_id, user_chain, accepted_quantity, amount_paid)
+            .create_and_record_bid(
+                auction_id,
+                user_chain,
+                bidder,
+                accepted_quantity,
+                amount_paid,
+                delivery,
+                payment_asset,
+                recipient,
+            )
             .await;
 
-        // Update auction state
-        self.update_auction_state(auction_id, accepted_quantity, is_first_bid, user_chain)
-            .await;
+        // Update auction state (the position belongs to whoever receives the allocation)
+        let holder = recipient.map_or(bidder, |recipient| recipient.owner);
+        self.update_auction_state(
+            auction_id,
+            accepted_quantity,
+            is_first_bid,
+            user_chain,
+            holder,
+            payment_asset,
+        )
+        .await;
 
         // Finalize bid processing (emit events, check settlement)
-        self.finalize_bid_processing(auction_id, &bid, current_price)
+        self.finalize_bid_processing(auction_id, &bid, requested_quantity, price_per_unit)
+            .await;
+
+        bid.bid_id
+    }
+
+    /// Allocate queued fairness-mode bids submitted at or before `horizon`
+    /// Bids are processed in submission-time order (ties keep arrival order).
+    /// Bids that can no longer be filled are rejected and their escrow refunded.
+    async fn drain_pending_bids(&mut self, auction_id: u64, horizon: Timestamp) {
+        let mut pending = self
+            .state
+            .pending_bids
+            .get(&auction_id)
+            .await
+            .unwrap()
+            .unwrap_or_default();
+
+        if pending.is_empty() {
+            return;
+        }
+
+        pending.sort_by_key(|bid| bid.submitted_at);
+        let mut waiting = Vec::new();
+
+        for bid in pending {
+            let auction = self.state.auctions.get(&auction_id).await.unwrap().unwrap();
+            let payment_asset = bid.payment_asset;
+            let is_active = auction.status == shared::types::AuctionStatus::Active;
+
+            let rejection = if !is_active {
+                Some("Auction not active".to_string())
+            } else if bid.submitted_at > auction.params.end_time {
+                Some(format!("Auction expired at: {:?}", auction.params.end_time))
+            } else if bid.submitted_at > horizon {
+                // Still inside the fairness window - an earlier submission may yet arrive
+                waiting.push(bid);
+                continue;
+            } else {
+                None
+            };
+
+            if let Some(reason) = rejection {
+                let event = AuctionEvent::BidRejected {
+                    auction_id,
+                    user_chain: bid.user_chain,
+                    reason,
+                };
+                self.runtime.emit(AUCTION_STREAM.into(), &event);
+                self.deliver_refund(
+                    auction_id,
+                    bid.bidder,
+                    bid.user_chain,
+                    bid.delivery,
+                    bid.escrowed,
+                    payment_asset,
+                )
+                .await;
+                continue;
+            }
+
+            let accepted_quantity = match self.validate_supply(
+                auction.total_supply,
+                auction.sold,
+                bid.quantity,
+                bid.fill_policy,
+                auction_id,
+                bid.user_chain,
+            ) {
+                Ok(qty) => qty,
+                Err(()) => {
+                    self.deliver_refund(
+                        auction_id,
+                        bid.bidder,
+                        bid.user_chain,
+                        bid.delivery,
+                        bid.escrowed,
+                        payment_asset,
+                    )
+                    .await;
+                    continue;
+                }
+            };
+
+            // Return escrow for any units that could not be allocated
+            let rate = auction
+                .params
+                .rate_for(payment_asset)
+                .expect("Queued bid paid in an accepted asset");
+            let amount_paid =
+                convert_to_asset(bid.price_per_unit.saturating_mul(accepted_quantity as u128), rate);
+            self.deliver_refund(
+                auction_id,
+                bid.bidder,
+                bid.user_chain,
+                bid.delivery,
+                bid.escrowed.saturating_sub(amount_paid),
+                payment_asset,
+            )
+            .await;
+
+            self.record_accepted_bid(
+                auction_id,
+                bid.user_chain,
+                bid.bidder,
+                bid.quantity,
+                accepted_quantity,
+                amount_paid,
+                bid.price_per_unit,
+                bid.delivery,
+                payment_asset,
+                bid.recipient,
+            )
             .await;
+        }
+
+        if waiting.is_empty() {
+            self.state.pending_bids.remove(&auction_id).unwrap();
+        } else {
+            self.state.pending_bids.insert(&auction_id, waiting).unwrap();
+        }
+    }
+
+    /// Keeper entry point: allocate fairness-mode bids whose window has passed,
+    /// then end any active auction whose end conditions are met
+    async fn handle_trigger(&mut self) -> AuctionResponse {
+        let now = self.runtime.system_time();
+        let auction_ids = self.state.pending_bids.indices().await.unwrap();
+
+        for auction_id in auction_ids {
+            let auction = self.state.auctions.get(&auction_id).await.unwrap().unwrap();
+            let window = auction.params.fair_ordering_window.unwrap_or(0);
+            let horizon = Timestamp::from(now.micros().saturating_sub(window));
+            self.drain_pending_bids(auction_id, horizon).await;
+        }
+
+        // Only auctions that have not ended yet (Scheduled ones wait for their first bid)
+        for auction_id in self.state.open_auctions.indices().await.unwrap() {
+            let auction = self.state.auctions.get(&auction_id).await.unwrap().unwrap();
+            if auction.status != shared::types::AuctionStatus::Active {
+                continue;
+            }
+
+            let current_price = self.calculate_current_price(auction_id).await;
+            if let Some(clear_reason) = self.end_reason(&auction, current_price, now) {
+                self.end_auction(auction_id, current_price, clear_reason).await;
+            }
+        }
+
+        // Push settlements for auto-distributing auctions, a bounded page of bidder positions per call
+        // (positions are incomplete until Migrate has finished)
+        const DISTRIBUTION_BUDGET: usize = 25;
+        let mut budget = if self.is_migrated().await { DISTRIBUTION_BUDGET } else { 0 };
+        for auction_id in self.state.pending_distributions.indices().await.unwrap() {
+            if budget == 0 {
+                break;
+            }
+            budget -= self.distribute_settlements(auction_id, budget).await;
+        }
+
+        AuctionResponse::Ok
+    }
+
+    /// Evaluate an active auction's end conditions (supply exhaustion is handled per bid)
+    /// Returns the reason to clear, or None if the auction stays open
+    fn end_reason(
+        &mut self,
+        auction: &AuctionData,
+        current_price: Amount,
+        now: Timestamp,
+    ) -> Option<ClearReason> {
+        if now > auction.params.end_time {
+            return Some(ClearReason::TimeExpired);
+        }
+
+        // Clock ticks (microseconds or blocks) elapsed since decay started
+        let elapsed = match auction.params.price_clock {
+            PriceClock::WallClock => now.delta_since(auction.params.start_time).as_micros(),
+            PriceClock::BlockHeight { start_height } => self
+                .runtime
+                .block_height()
+                .0
+                .saturating_sub(start_height.0),
+        };
+
+        for condition in &auction.params.end_conditions {
+            match condition {
+                EndCondition::FloorReachedFor(duration) => {
+                    let floor_reached = shared::floor_reached_after(
+                        auction.params.start_price,
+                        auction.params.floor_price,
+                        auction.params.price_decay_amount,
+                        auction.params.price_decay_interval,
+                    );
+                    if let Some(floor_reached) = floor_reached {
+                        if elapsed >= floor_reached.saturating_add(*duration) {
+                            return Some(ClearReason::FloorDurationElapsed);
+                        }
+                    }
+                }
+                EndCondition::RaiseTarget(target) => {
+                    let raised = current_price.saturating_mul(auction.sold as u128);
+                    if raised >= *target {
+                        return Some(ClearReason::RaiseTargetReached);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// End an active auction: allocate queued fairness-mode bids, then clear and settle
+    async fn end_auction(&mut self, auction_id: u64, clearing_price: Amount, reason: ClearReason) {
+        let auction = self.state.auctions.get(&auction_id).await.unwrap().unwrap();
+
+        // Fairness mode: allocate bids submitted before the auction ended first
+        if auction.params.fair_ordering_window.is_some() {
+            let horizon = self.runtime.system_time().min(auction.params.end_time);
+            self.drain_pending_bids(auction_id, horizon).await;
+        }
+
+        self.clear_auction(auction_id, clearing_price, reason).await;
+    }
+
+    /// Clear an active auction at the given price and settle it
+    async fn clear_auction(&mut self, auction_id: u64, clearing_price: Amount, reason: ClearReason) {
+        let auction = self.state.auctions.get_mut(&auction_id).await.unwrap().unwrap();
+
+        // Already cleared (e.g. supply exhausted while draining queued bids)
+        if auction.status != shared::types::AuctionStatus::Active {
+            return;
+        }
+
+        auction.clearing_price = Some(clearing_price);
+        auction.status = shared::types::AuctionStatus::Ended;
+        let total_bids = auction.total_bids;
+        self.state.open_auctions.remove(&auction_id).unwrap();
+
+        let event = AuctionEvent::AuctionCleared {
+            auction_id,
+            clearing_price,
+            total_bids,
+            reason,
+        };
+        self.runtime.emit(AUCTION_STREAM.into(), &event);
+
+        self.settle_auction(auction_id).await;
     }
 
     /// Settle auction (manual claim-based settlement - no auto-messaging)
@@ -684,18 +3266,28 @@ impl AuctionContract {
         let clearing_price = auction.clearing_price.expect("Clearing price not set");
         let total_bidders = auction.total_bidders;
         let total_sold = auction.sold;
+        let auto_distribute = auction.params.auto_distribute;
+        let bid_digest = auction.bid_digest;
+        let digest_start = auction.digest_start;
 
         // Update auction status to Settled
         auction.status = shared::types::AuctionStatus::Settled;
         auction.settled_at = Some(self.runtime.system_time());
+        if auto_distribute {
+            self.state.pending_distributions.insert(&auction_id, ()).unwrap();
+        }
 
         // Emit settlement event
-        // NOTE: Users must manually claim their settlements via ClaimSettlement operation
+        // NOTE: Users claim their settlements via ClaimSettlement, unless the auction
+        // auto-distributes - then Trigger pushes them to every bidder in pages.
+        // The creator collects the sale proceeds separately with ClaimProceeds.
         let event = AuctionEvent::AuctionSettled {
             auction_id,
             clearing_price,
             total_bidders,
             total_sold,
+            bid_digest,
+            digest_start,
         };
         self.runtime.emit(AUCTION_STREAM.into(), &event);
     }
@@ -741,27 +3333,70 @@ impl AuctionContract {
             return Err(());
         }
 
-        // Check if auction is active
-        if current_status != shared::types::AuctionStatus::Active {
-            let event = AuctionEvent::BidRejected {
-                auction_id,
-                user_chain,
-                reason: "Auction not active".to_string(),
-            };
-            self.runtime.emit(AUCTION_STREAM.into(), &event);
-            return Err(());
+        // Check if auction is active
+        if current_status != shared::types::AuctionStatus::Active {
+            let event = AuctionEvent::BidRejected {
+                auction_id,
+                user_chain,
+                reason: "Auction not active".to_string(),
+            };
+            self.runtime.emit(AUCTION_STREAM.into(), &event);
+            return Err(());
+        }
+
+        Ok(None)
+    }
+
+    /// Validate the bidder's max price and deadline against the AAC's view of the auction
+    /// Returns Ok(()) if the bid is within limits, Err if rejected
+    fn validate_bid_limits(
+        &mut self,
+        current_price: Amount,
+        max_price_per_unit: Option<Amount>,
+        deadline: Option<Timestamp>,
+        now: Timestamp,
+        auction_id: u64,
+        user_chain: ChainId,
+    ) -> Result<(), ()> {
+        if let Some(deadline) = deadline {
+            if now > deadline {
+                let event = AuctionEvent::BidRejected {
+                    auction_id,
+                    user_chain,
+                    reason: format!("Bid deadline passed at: {:?}", deadline),
+                };
+                self.runtime.emit(AUCTION_STREAM.into(), &event);
+                return Err(());
+            }
+        }
+
+        if let Some(max_price) = max_price_per_unit {
+            if current_price > max_price {
+                let event = AuctionEvent::BidRejected {
+                    auction_id,
+                    user_chain,
+                    reason: format!(
+                        "Price exceeds max price per unit: current {}, max {}",
+                        current_price, max_price
+                    ),
+                };
+                self.runtime.emit(AUCTION_STREAM.into(), &event);
+                return Err(());
+            }
         }
 
-        Ok(None)
+        Ok(())
     }
 
     /// Validate supply availability and return accepted quantity
-    /// Returns Ok(accepted_quantity) if supply available, Err if supply exhausted
+    /// Returns Ok(accepted_quantity) if supply available and the fill policy is satisfied,
+    /// Err if supply exhausted or the bid cannot be filled as requested
     fn validate_supply(
         &mut self,
         total_supply: u64,
         sold: u64,
         requested_quantity: u64,
+        fill_policy: FillPolicy,
         auction_id: u64,
         user_chain: ChainId,
     ) -> Result<u64, ()> {
@@ -777,17 +3412,44 @@ impl AuctionContract {
             return Err(());
         }
 
-        Ok(requested_quantity.min(remaining))
+        let accepted_quantity = requested_quantity.min(remaining);
+
+        // Enforce the bidder's fill policy
+        let minimum_fill = match fill_policy {
+            FillPolicy::Partial => 0,
+            FillPolicy::AllOrNothing => requested_quantity,
+            FillPolicy::MinimumFill(minimum) => minimum.min(requested_quantity),
+        };
+
+        if accepted_quantity < minimum_fill {
+            let event = AuctionEvent::BidRejected {
+                auction_id,
+                user_chain,
+                reason: format!(
+                    "Fill policy not met: requested {}, minimum {}, remaining {}",
+                    requested_quantity, minimum_fill, remaining
+                ),
+            };
+            self.runtime.emit(AUCTION_STREAM.into(), &event);
+            return Err(());
+        }
+
+        Ok(accepted_quantity)
     }
 
     /// Create bid record and insert into storage
     /// Returns (bid_record, is_first_bid_from_user)
+    #[allow(clippy::too_many_arguments)]
     async fn create_and_record_bid(
         &mut self,
         auction_id: u64,
         user_chain: ChainId,
+        bidder: AccountOwner,
         quantity: u64,
         amount_paid: Amount,
+        delivery: SettlementDelivery,
+        payment_asset: PaymentAsset,
+        recipient: Option<Account>,
     ) -> (BidRecord, bool) {
         // Create bid record
         let bid_id = *self.state.next_bid_id.get();
@@ -797,30 +3459,220 @@ impl AuctionContract {
             bid_id,
             auction_id,
             user_chain,
+            bidder,
             quantity,
             amount_paid,
             timestamp: self.runtime.system_time(),
             claimed: false,
+            delivery,
+            payment_asset,
+            recipient,
         };
 
-        // Insert bid using composite key for O(1) lookups
-        let mut user_bids = self
-            .state
-            .user_auction_bids
-            .get(&(user_chain, auction_id))
+        // Append to the auction's bid log (never rewritten) and extend its hash chain
+        self.state
+            .bid_log
+            .load_entry_mut(&auction_id)
             .await
             .unwrap()
-            .unwrap_or_default();
+            .push(bid.clone());
+        let auction = self.state.auctions.get_mut(&auction_id).await.unwrap().unwrap();
+        auction.bid_digest = Some(chain_bid_digest(auction.bid_digest, &bid));
+
+        let is_first_bid_from_user = self.record_position(&bid).await;
+
+        (bid, is_first_bid_from_user)
+    }
+
+    /// Upgrade up to `max_entries` legacy records, returning how many were upgraded
+    /// Steps run in order, each draining one original-layout map; an empty map finishes its step
+    /// and the schema version moves on. The AAC drains auctions and bids, user chains
+    /// their commitments; the other steps find nothing on that chain and pass straight through.
+    async fn handle_migrate(&mut self, max_entries: usize) -> usize {
+        assert!(max_entries > 0, "max_entries must be greater than 0");
+
+        let mut entries = 0;
+        while entries < max_entries {
+            let version = *self.state.schema_version.get();
+            if version >= SCHEMA_VERSION {
+                break;
+            }
+
+            let budget = max_entries - entries;
+            let migrated = match version {
+                // Auctions into the current AuctionData layout
+                0 => self.migrate_legacy_auctions(budget).await,
+                // Legacy per-bid vectors into positions and the bid log
+                1 => self.migrate_legacy_bids(budget).await,
+                // UIC commitments into the current UserCommitment layout
+                2 => self.migrate_legacy_commitments(budget).await,
+                _ => unreachable!("No migration from schema version {}", version),
+            };
+            entries += migrated;
+
+            if migrated < budget {
+                self.state.schema_version.set(version + 1);
+                self.state.migration_cursor.set(0);
+            } else {
+                *self.state.migration_cursor.get_mut() += migrated as u64;
+            }
+        }
+        entries
+    }
+
+    /// Move up to `max_entries` auctions out of legacy_auctions, returning how many were moved
+    /// Auctions still open are indexed for Trigger.
+    async fn migrate_legacy_auctions(&mut self, max_entries: usize) -> usize {
+        let mut batch = Vec::new();
+        self.state
+            .legacy_auctions
+            .for_each_index_while(|auction_id| {
+                batch.push(auction_id);
+                Ok(batch.len() < max_entries)
+            })
+            .await
+            .unwrap();
+
+        for auction_id in &batch {
+            let auction = self
+                .state
+                .legacy_auctions
+                .get(auction_id)
+                .await
+                .unwrap()
+                .expect("Indexed legacy auction")
+                .into_auction_data();
+
+            if matches!(
+                auction.status,
+                shared::types::AuctionStatus::Scheduled | shared::types::AuctionStatus::Active
+            ) {
+                self.state.open_auctions.insert(auction_id, ()).unwrap();
+            }
+            self.state.auctions.insert(auction_id, auction).unwrap();
+            self.state.legacy_auctions.remove(auction_id).unwrap();
+        }
+
+        batch.len()
+    }
+
+    /// Move up to `max_entries` commitments out of legacy_commitments, returning how many
+    /// were moved (UIC only)
+    async fn migrate_legacy_commitments(&mut self, max_entries: usize) -> usize {
+        let mut batch = Vec::new();
+        self.state
+            .legacy_commitments
+            .for_each_index_while(|auction_id| {
+                batch.push(auction_id);
+                Ok(batch.len() < max_entries)
+            })
+            .await
+            .unwrap();
+
+        for auction_id in &batch {
+            self.load_commitment(*auction_id).await;
+        }
 
-        let is_first_bid_from_user = user_bids.is_empty();
-        user_bids.push(bid.clone());
+        batch.len()
+    }
 
+    /// Move up to `max_entries` legacy (user_chain, auction_id) bid vectors into
+    /// positions and the bid log, returning how many were moved
+    /// Each chain's legacy bids become one position owned by the chain itself.
+    /// Migrated bids are not added to the auction's bid digest: their acceptance order is lost.
+    async fn migrate_legacy_bids(&mut self, max_entries: usize) -> usize {
+        let mut batch = Vec::new();
         self.state
             .user_auction_bids
-            .insert(&(user_chain, auction_id), user_bids)
+            .for_each_index_while(|key| {
+                batch.push(key);
+                Ok(batch.len() < max_entries)
+            })
+            .await
             .unwrap();
 
-        (bid, is_first_bid_from_user)
+        for key in &batch {
+            let (_, auction_id) = *key;
+            let payment_asset = self
+                .state
+                .auctions
+                .get(&auction_id)
+                .await
+                .unwrap()
+                .expect("Legacy bids for an unknown auction")
+                .params
+                .payment_asset;
+            let bids: Vec<BidRecord> = self
+                .state
+                .user_auction_bids
+                .get(key)
+                .await
+                .unwrap()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|bid| bid.into_bid(payment_asset))
+                .collect();
+
+            for bid in &bids {
+                self.state
+                    .bid_log
+                    .load_entry_mut(&bid.auction_id)
+                    .await
+                    .unwrap()
+                    .push(bid.clone());
+                self.record_position(bid).await;
+            }
+
+            // Legacy claims covered all of an owner's bids at once
+            for bid in bids.iter().filter(|bid| bid.claimed) {
+                let position_key = (bid.user_chain, bid.auction_id, bid.bidder);
+                if let Some(position) = self.state.positions.get_mut(&position_key).await.unwrap() {
+                    position.claimed = true;
+                }
+            }
+
+            self.state.user_auction_bids.remove(key).unwrap();
+        }
+
+        batch.len()
+    }
+
+    /// Fold a bid into its owner's aggregate position, indexing new positions under the auction
+    /// Returns true if this is the first bid on the auction from the bid's chain
+    async fn record_position(&mut self, bid: &BidRecord) -> bool {
+        let key = (bid.user_chain, bid.auction_id, bid.bidder);
+        let existing = self.state.positions.get(&key).await.unwrap();
+        let is_new_position = existing.is_none();
+        let mut position = existing.unwrap_or_default();
+        let is_new_gift = bid.recipient.is_some_and(|recipient| {
+            !position.gifts.iter().any(|gift| gift.recipient == recipient)
+        });
+        position.record_bid(bid);
+        self.state.positions.insert(&key, position).unwrap();
+
+        // Let the recipient find the gift without knowing who paid for it
+        if let (Some(recipient), true) = (bid.recipient, is_new_gift) {
+            self.state
+                .gift_positions
+                .get_mut_or_default(&(recipient, bid.auction_id))
+                .await
+                .unwrap()
+                .push((bid.user_chain, bid.bidder));
+        }
+
+        let mut bidder_positions = self.bidder_positions(bid.auction_id).await;
+        let is_first_bid_from_chain = !bidder_positions
+            .iter()
+            .any(|(chain_id, _)| *chain_id == bid.user_chain);
+        if is_new_position {
+            bidder_positions.push((bid.user_chain, bid.bidder));
+            self.state
+                .bidder_positions
+                .insert(&bid.auction_id, bidder_positions)
+                .unwrap();
+        }
+
+        is_first_bid_from_chain
     }
 
     /// Update auction state with new bid
@@ -830,12 +3682,25 @@ impl AuctionContract {
         quantity: u64,
         is_first_bid: bool,
         user_chain: ChainId,
+        holder: AccountOwner,
+        payment_asset: PaymentAsset,
     ) {
         // Re-acquire auction reference to update counters
         let auction = self.state.auctions.get_mut(&auction_id).await.unwrap().unwrap();
 
         // Update sold quantity and cached counters
         auction.sold += quantity;
+        match auction
+            .sold_by_asset
+            .iter_mut()
+            .find(|sold| sold.payment_asset == payment_asset)
+        {
+            Some(sold) => sold.quantity += quantity,
+            None => auction.sold_by_asset.push(AssetQuantity {
+                payment_asset,
+                quantity,
+            }),
+        }
         auction.total_bids += 1;
         if is_first_bid {
             auction.total_bidders += 1;
@@ -857,6 +3722,20 @@ impl AuctionContract {
             .user_totals
             .insert(&(auction_id, user_chain), user_total + quantity)
             .unwrap();
+
+        // Update owner total
+        let owner_total = self
+            .state
+            .owner_totals
+            .get(&(holder, auction_id))
+            .await
+            .unwrap()
+            .unwrap_or(0);
+
+        self.state
+            .owner_totals
+            .insert(&(holder, auction_id), owner_total + quantity)
+            .unwrap();
     }
 
     /// Finalize bid processing: emit events and check settlement
@@ -864,6 +3743,7 @@ impl AuctionContract {
         &mut self,
         auction_id: u64,
         bid: &BidRecord,
+        requested_quantity: u64,
         current_price: Amount,
     ) {
         // Emit payment received event
@@ -876,40 +3756,38 @@ impl AuctionContract {
         self.runtime.emit(AUCTION_STREAM.into(), &payment_event);
 
         // Get auction for event data
-        let auction = self.state.auctions.get_mut(&auction_id).await.unwrap().unwrap();
+        let auction = self.state.auctions.get(&auction_id).await.unwrap().unwrap();
 
         // Emit bid accepted event
         let event = AuctionEvent::BidAccepted {
             auction_id,
             bid_id: bid.bid_id,
             user_chain: bid.user_chain,
+            bidder: bid.bidder,
+            requested_quantity,
             quantity: bid.quantity,
             amount_paid: bid.amount_paid,
             total_sold: auction.sold,
             remaining: auction.total_supply - auction.sold,
+            block_height: self.runtime.block_height(),
+            delivery: bid.delivery,
+            payment_asset: bid.payment_asset,
+            recipient: bid.recipient,
         };
         self.runtime.emit(AUCTION_STREAM.into(), &event);
 
-        // Check if supply exhausted and trigger settlement
-        let supply_exhausted = auction.sold >= auction.total_supply;
-        if supply_exhausted {
-            auction.clearing_price = Some(current_price);
-            auction.status = shared::types::AuctionStatus::Ended;
-            let total_bids = auction.total_bids;
-
-            let event = AuctionEvent::AuctionCleared {
-                auction_id,
-                clearing_price: current_price,
-                total_bids,
-                reason: ClearReason::SupplyExhausted,
-            };
-            self.runtime.emit(AUCTION_STREAM.into(), &event);
-
-            // Drop auction reference before calling settle_auction
-            let _ = auction;
+        // Check if supply exhausted (or another end condition met) and trigger settlement
+        let clear_reason = if auction.sold >= auction.total_supply {
+            Some(ClearReason::SupplyExhausted)
+        } else {
+            let now = self.runtime.system_time();
+            self.end_reason(&auction, current_price, now)
+        };
 
-            // Auto-settle
-            self.settle_auction(auction_id).await;
+        if let Some(clear_reason) = clear_reason {
+            // Clear and auto-settle
+            self.clear_auction(auction_id, current_price, clear_reason)
+                .await;
         }
     }
 
@@ -923,7 +3801,7 @@ impl AuctionContract {
         &mut self,
         bidder: AccountOwner,
         amount: Amount,
-        payment_token_app: ApplicationId,
+        payment_asset: PaymentAsset,
     ) -> Result<(), String> {
         // Define escrow account owned by the application
         let escrow_account = Account {
@@ -932,73 +3810,362 @@ impl AuctionContract {
         };
 
         // Transfer from bidder (on AAC) to escrow (on AAC) - synchronous
-        let transfer_operation = FungibleOperation::Transfer {
-            owner: bidder,
-            amount,
-            target_account: escrow_account,
+        self.transfer_asset(payment_asset, bidder, amount, escrow_account)
+    }
+
+    /// Helper: Collect payment to escrow from `owner`'s allowance to the auction app (synchronous on AAC)
+    /// Only fungible tokens support allowances.
+    fn collect_payment_from_allowance(
+        &mut self,
+        owner: AccountOwner,
+        amount: Amount,
+        payment_asset: PaymentAsset,
+    ) -> Result<(), String> {
+        let PaymentAsset::Fungible(token_app) = payment_asset else {
+            return Err("Allowances require a fungible payment asset".to_string());
         };
 
-        // Convert untyped ApplicationId to typed for the call
-        let typed_app: ApplicationId<FungibleTokenAbi> = unsafe {
-            std::mem::transmute(payment_token_app)
+        let app_owner: AccountOwner = self.runtime.application_id().into();
+        let transfer_operation = FungibleOperation::TransferFrom {
+            owner,
+            spender: app_owner,
+            amount,
+            target_account: Account {
+                chain_id: self.runtime.chain_id(), // AAC chain
+                owner: app_owner, // App-owned escrow
+            },
         };
+        let typed_app = token_app.with_abi::<FungibleTokenAbi>();
 
-        // Call fungible token application (synchronous - same chain)
-        // This will fail immediately if user has insufficient balance
+        // Fails immediately if the allowance or balance is insufficient
         match self.runtime.call_application(true, typed_app, &transfer_operation) {
             FungibleResponse::Ok => Ok(()),
-            FungibleResponse::Balance(_) | FungibleResponse::TickerSymbol(_) | FungibleResponse::TokenName(_) => {
+            FungibleResponse::Balance(_)
+            | FungibleResponse::TickerSymbol(_)
+            | FungibleResponse::TokenName(_)
+            | FungibleResponse::Migrated(_) => {
                 Err("Unexpected response from fungible token".to_string())
             }
         }
     }
 
-    /// Helper: Refund excess payment to user after settlement (synchronous on AAC)
-    fn refund_payment(
+    /// Helper: Move `amount` of `payment_asset` from `source` to `target_account`
+    /// Native tokens use the system transfer API, fungible tokens call the token application.
+    /// Transfers to another chain are delivered with a cross-chain Credit.
+    fn transfer_asset(
+        &mut self,
+        payment_asset: PaymentAsset,
+        source: AccountOwner,
+        amount: Amount,
+        target_account: Account,
+    ) -> Result<(), String> {
+        match payment_asset {
+            PaymentAsset::Native => {
+                // System transfers abort the block on failure, so check the balance first
+                let balance = self.runtime.owner_balance(source);
+                if balance < amount {
+                    return Err(format!("Insufficient native balance: {}", balance));
+                }
+                self.runtime.transfer(source, target_account, amount);
+                Ok(())
+            }
+            PaymentAsset::Fungible(token_app) => {
+                let transfer_operation = FungibleOperation::Transfer {
+                    owner: source,
+                    amount,
+                    target_account,
+                };
+                let typed_app = token_app.with_abi::<FungibleTokenAbi>();
+
+                // Call fungible token application (synchronous - same chain)
+                // This will fail immediately if the source has insufficient balance
+                match self.runtime.call_application(true, typed_app, &transfer_operation) {
+                    FungibleResponse::Ok => Ok(()),
+                    FungibleResponse::Balance(_)
+                    | FungibleResponse::TickerSymbol(_)
+                    | FungibleResponse::TokenName(_)
+                    | FungibleResponse::Migrated(_) => {
+                        Err("Unexpected response from fungible token".to_string())
+                    }
+                }
+            }
+        }
+    }
+
+    /// Helper: Deliver a refund according to the bid's delivery preference
+    async fn deliver_refund(
         &mut self,
         auction_id: u64,
         bidder: AccountOwner,
+        user_chain: ChainId,
+        delivery: SettlementDelivery,
         refund_amount: Amount,
-        payment_token_app: ApplicationId,
+        payment_asset: PaymentAsset,
     ) {
-        if refund_amount == Amount::ZERO {
-            return; // No refund needed
-        }
-
-        // User account on AAC (refund stays on AAC for fast settlement)
-        let user_account = Account {
-            chain_id: self.runtime.chain_id(), // AAC
-            owner: bidder,
-        };
-
-        // Transfer from escrow (app-owned) back to user
-        let transfer_operation = FungibleOperation::Transfer {
-            owner: self.runtime.application_id().into(), // From app escrow
-            amount: refund_amount,
-            target_account: user_account,
-        };
+        let Some(user_account) = self.refund_account(bidder, user_chain, delivery) else {
+            // Roll the refund into the bidder's deposit ledger - no token transfer
+            if refund_amount > Amount::ZERO {
+                self.credit_deposit(bidder, payment_asset, refund_amount)
+                    .await;
 
-        // Convert untyped ApplicationId to typed for the call
-        let typed_app: ApplicationId<FungibleTokenAbi> = unsafe {
-            std::mem::transmute(payment_token_app)
-        };
-
-        // Call fungible token application (synchronous - same chain)
-        match self.runtime.call_application(true, typed_app, &transfer_operation) {
-            FungibleResponse::Ok => {
-                // Emit refund event
                 let event = AuctionEvent::RefundIssued {
                     auction_id,
-                    user_chain: self.runtime.chain_id(), // AAC
+                    user_chain: self.runtime.chain_id(), // Held on AAC
                     refund_amount,
                 };
                 self.runtime.emit(AUCTION_STREAM.into(), &event);
             }
-            _ => {
-                // This should not fail since escrow has the funds
-                panic!("Failed to refund payment to user");
-            }
+            return;
+        };
+
+        self.refund_payment(auction_id, user_account, refund_amount, payment_asset);
+    }
+
+    /// Account a refund is transferred to, or None for the deposit ledger
+    fn refund_account(
+        &mut self,
+        bidder: AccountOwner,
+        user_chain: ChainId,
+        delivery: SettlementDelivery,
+    ) -> Option<Account> {
+        let chain_id = match delivery {
+            SettlementDelivery::Aac => self.runtime.chain_id(), // Refund stays on AAC for fast settlement
+            SettlementDelivery::UserChain => user_chain,
+            SettlementDelivery::Deposit => return None,
+        };
+
+        Some(Account {
+            chain_id,
+            owner: bidder,
+        })
+    }
+
+    // ═══════════════════════════════════════════════════════════
+    // Deposit Ledger Helper Methods
+    // ═══════════════════════════════════════════════════════════
+
+    /// Handle a deposit credited to the ledger (funds already in escrow)
+    async fn credit_deposit(
+        &mut self,
+        owner: AccountOwner,
+        payment_asset: PaymentAsset,
+        amount: Amount,
+    ) {
+        let balance = self
+            .state
+            .deposits
+            .get_mut_or_default(&(owner, payment_asset))
+            .await
+            .unwrap();
+        balance.saturating_add_assign(amount);
+    }
+
+    /// Debit the ledger, returning Err if the balance is insufficient
+    async fn debit_deposit(
+        &mut self,
+        owner: AccountOwner,
+        payment_asset: PaymentAsset,
+        amount: Amount,
+    ) -> Result<(), String> {
+        let key = (owner, payment_asset);
+        let mut balance = self.state.deposits.get(&key).await.unwrap().unwrap_or_default();
+
+        balance
+            .try_sub_assign(amount)
+            .map_err(|_| format!("Insufficient deposit balance: {}", balance))?;
+
+        if balance == Amount::ZERO {
+            self.state.deposits.remove(&key).unwrap();
+        } else {
+            self.state.deposits.insert(&key, balance).unwrap();
+        }
+
+        Ok(())
+    }
+
+    /// Record or forward the signer's BuyFor authorisation for `bidder`
+    fn forward_bidder_authorisation(&mut self, bidder: AccountOwner, authorised: bool) {
+        let app_params = self.runtime.application_parameters();
+        let beneficiary = self
+            .runtime
+            .authenticated_signer()
+            .expect("Bidder authorisation must be called by authenticated user");
+
+        if self.runtime.chain_id() == app_params.aac_chain {
+            self.set_bidder_authorisation(beneficiary, bidder, authorised);
+        } else {
+            let message = if authorised {
+                AuctionMessage::AuthorizeBidder { bidder }
+            } else {
+                AuctionMessage::RevokeBidder { bidder }
+            };
+            self.runtime
+                .prepare_message(message)
+                .with_authentication()
+                .send_to(app_params.aac_chain);
+        }
+    }
+
+    /// Index a chain holding BuyFor positions for `beneficiary` (AAC only)
+    async fn record_delegated_position(
+        &mut self,
+        beneficiary: AccountOwner,
+        auction_id: u64,
+        user_chain: ChainId,
+    ) {
+        let chains = self
+            .state
+            .delegated_positions
+            .get_mut_or_default(&(beneficiary, auction_id))
+            .await
+            .unwrap();
+        if !chains.contains(&user_chain) {
+            chains.push(user_chain);
+        }
+    }
+
+    /// Positions a signer on `user_chain` may claim for an auction, as (chain, owner) keys:
+    /// its own, the chain's migrated legacy bids, and BuyFor positions placed for it elsewhere
+    async fn claimable_positions(
+        &self,
+        bidder: AccountOwner,
+        auction_id: u64,
+        user_chain: ChainId,
+    ) -> Vec<(ChainId, AccountOwner)> {
+        let mut positions = vec![(user_chain, bidder)];
+        if bidder != AccountOwner::CHAIN {
+            positions.push((user_chain, AccountOwner::CHAIN));
+        }
+
+        let delegated = self
+            .state
+            .delegated_positions
+            .get(&(bidder, auction_id))
+            .await
+            .unwrap()
+            .unwrap_or_default();
+        positions.extend(
+            delegated
+                .into_iter()
+                .filter(|chain| *chain != user_chain)
+                .map(|chain| (chain, bidder)),
+        );
+        positions
+    }
+
+    /// Whether Migrate has moved every legacy AAC record (claims and bids read only positions)
+    /// A chain that never held original-layout records has nothing to wait for.
+    async fn is_migrated(&self) -> bool {
+        if *self.state.schema_version.get() >= SCHEMA_VERSION {
+            return true;
+        }
+
+        let mut legacy = false;
+        self.state
+            .legacy_auctions
+            .for_each_index_while(|_| {
+                legacy = true;
+                Ok(false)
+            })
+            .await
+            .unwrap();
+        self.state
+            .user_auction_bids
+            .for_each_index_while(|_| {
+                legacy = true;
+                Ok(false)
+            })
+            .await
+            .unwrap();
+        !legacy
+    }
+
+    /// Refuse to touch positions while legacy records are still being migrated
+    async fn assert_migrated(&self) {
+        assert!(
+            self.is_migrated().await,
+            "State migration in progress. Run Migrate until it completes"
+        );
+    }
+
+    /// Add or remove a (beneficiary, bidder) BuyFor delegation (AAC only)
+    fn set_bidder_authorisation(
+        &mut self,
+        beneficiary: AccountOwner,
+        bidder: AccountOwner,
+        authorised: bool,
+    ) {
+        let key = (beneficiary, bidder);
+        if authorised {
+            self.state.authorised_bidders.insert(&key, ()).unwrap();
+        } else {
+            self.state.authorised_bidders.remove(&key).unwrap();
+        }
+    }
+
+    /// Handle a withdrawal from the ledger to the owner's account on `target_chain`
+    async fn handle_withdraw_deposit(
+        &mut self,
+        owner: AccountOwner,
+        payment_asset: PaymentAsset,
+        amount: Amount,
+        target_chain: ChainId,
+    ) {
+        self.debit_deposit(owner, payment_asset, amount)
+            .await
+            .expect("Withdrawal exceeds deposit balance");
+
+        let target_account = Account {
+            chain_id: target_chain,
+            owner,
+        };
+        let escrow_owner = self.runtime.application_id().into(); // From app escrow
+        self.transfer_asset(payment_asset, escrow_owner, amount, target_account)
+            .expect("Failed to withdraw deposit");
+    }
+
+    /// Helper: Move funds from the signer's balance on this (user) chain into the AAC escrow
+    /// They are delivered with a cross-chain Credit message
+    fn escrow_from_user_chain(&mut self, payment_asset: PaymentAsset, amount: Amount) {
+        let owner = self
+            .runtime
+            .authenticated_signer()
+            .expect("Escrow must be called by authenticated user");
+        let escrow_account = Account {
+            chain_id: self.runtime.application_parameters().aac_chain,
+            owner: self.runtime.application_id().into(),
+        };
+
+        self.transfer_asset(payment_asset, owner, amount, escrow_account)
+            .expect("Failed to escrow payment from user chain");
+    }
+
+    /// Helper: Refund excess payment to user after settlement
+    /// Synchronous on AAC; a cross-chain Credit is sent when the destination is another chain
+    fn refund_payment(
+        &mut self,
+        auction_id: u64,
+        user_account: Account,
+        refund_amount: Amount,
+        payment_asset: PaymentAsset,
+    ) {
+        if refund_amount == Amount::ZERO {
+            return; // No refund needed
         }
+
+        // Transfer from escrow (app-owned) back to user
+        // This should not fail since escrow has the funds
+        let escrow_owner = self.runtime.application_id().into();
+        self.transfer_asset(payment_asset, escrow_owner, refund_amount, user_account)
+            .expect("Failed to refund payment to user");
+
+        // Emit refund event
+        let event = AuctionEvent::RefundIssued {
+            auction_id,
+            user_chain: user_account.chain_id, // Destination chain
+            refund_amount,
+        };
+        self.runtime.emit(AUCTION_STREAM.into(), &event);
     }
 
 }
@@ -1,8 +1,14 @@
 use async_graphql::{Request, Response};
-use linera_sdk::linera_base_types::{ChainId, ContractAbi, ServiceAbi};
+use linera_sdk::linera_base_types::{
+    Account, AccountOwner, Amount, ApplicationId, ChainId, ContractAbi, CryptoHash, ServiceAbi,
+    Timestamp,
+};
 use linera_sdk::graphql::GraphQLMutationRoot;
 use serde::{Deserialize, Serialize};
-use shared::types::AuctionParamsInput;
+use shared::types::{
+    AuctionParamsInput, BasketLeg, FillPolicy, PaymentAsset, PaymentMethod, SettlementDelivery,
+    UnclaimedPolicy,
+};
 
 pub use shared;
 
@@ -23,8 +29,14 @@ impl ServiceAbi for AuctionAbi {
 
 /// Operations that can be executed on the Auction Application
 /// Different operations are relevant for different chain types:
-/// - AAC Chain: CreateAuction, PruneSettledAuction, CancelAuction
-/// - UIC Chains: Buy, SubscribeToAuction, UnsubscribeFromAuction, ClaimSettlement
+/// - AAC Chain: CreateAuction, PruneSettledAuction, SweepUnclaimed, CancelAuction, ClaimProceeds,
+///   AddPaymentToken, RemovePaymentToken, TransferAdmin, RegisterIndexer, UnregisterIndexer,
+///   SetUnclaimedPolicy
+/// - Indexer Chains: ConfirmArchive (called by the indexer application)
+/// - UIC Chains: Buy, BuyFor, BuyBasket, SubscribeToAuction, UnsubscribeFromAuction,
+///   ClaimSettlement, ClaimAll
+/// - Both: Deposit, Withdraw (deposit ledger held on the AAC), AuthorizeBidder, RevokeBidder,
+///   pool operations (pools held on the AAC), Migrate
 #[derive(Debug, Deserialize, Serialize, GraphQLMutationRoot)]
 pub enum AuctionOperation {
     // ─────────────────────────────────────────────────────────
@@ -36,16 +48,83 @@ pub enum AuctionOperation {
         params: AuctionParamsInput,
     },
 
-    /// Prune settled auction bids (after Indexer archives)
+    /// Prune settled auction bids (once a registered indexer has confirmed its archive)
+    /// Unclaimed bids are kept until SweepUnclaimed has finished.
     PruneSettledAuction {
         auction_id: u64,
     },
 
+    /// Settle every unclaimed bid once the auction's claim window has passed (AAC chain only)
+    /// Refunds follow the protocol's UnclaimedPolicy. Each call sweeps one page of bidder
+    /// positions; repeat until the auction reports unclaimed_swept.
+    SweepUnclaimed {
+        auction_id: u64,
+    },
+
+    /// Upgrade up to max_entries stored records towards the current schema version
+    /// (admin on the AAC chain, a chain owner on user chains). Repeat until
+    /// migrationStatus reports complete.
+    Migrate {
+        max_entries: u64,
+    },
+
+    /// Set what SweepUnclaimed does with unclaimed refunds (AAC chain only, admin only)
+    SetUnclaimedPolicy {
+        policy: UnclaimedPolicy,
+    },
+
     /// Cancel an auction (AAC chain only, creator only, before start or zero bids)
     CancelAuction {
         auction_id: u64,
     },
 
+    /// Pay a settled auction's sale proceeds out of escrow to the creator's AAC account
+    /// (AAC chain only, creator only, once per auction)
+    ClaimProceeds {
+        auction_id: u64,
+    },
+
+    /// Hand the admin role to another owner (AAC chain only, admin only)
+    /// If no admin is set yet, any owner of the AAC chain may run admin operations and becomes the admin.
+    TransferAdmin {
+        new_admin: AccountOwner,
+    },
+
+    /// Approve a fungible token for auction payments (AAC chain only, admin only)
+    AddPaymentToken {
+        token_app: ApplicationId,
+        symbol: String,
+        decimals: u8,
+    },
+
+    /// Remove a token from the registry (AAC chain only, admin only)
+    /// Existing auctions paid in it keep settling normally.
+    RemovePaymentToken {
+        token_app: ApplicationId,
+    },
+
+    /// Accept archive confirmations from the indexer application indexer_app on chain_id
+    /// (AAC chain only, admin only)
+    RegisterIndexer {
+        chain_id: ChainId,
+        indexer_app: ApplicationId,
+    },
+
+    /// Stop accepting archive confirmations from chain_id (AAC chain only, admin only)
+    UnregisterIndexer {
+        chain_id: ChainId,
+    },
+
+    /// Tell the AAC this chain's indexer holds a settled auction's full bid history
+    /// (indexer chain, called by the registered indexer application; the AAC checks the caller)
+    ConfirmArchive {
+        auction_id: u64,
+        bid_count: u64,
+        digest: Option<CryptoHash>,
+    },
+
+    /// Keeper operation: process time-dependent work (e.g. queued fairness-mode bids,
+    /// ending expired auctions, pushing settlements of auto-distributing auctions)
     Trigger,
 
     // ─────────────────────────────────────────────────────────
@@ -53,9 +132,51 @@ pub enum AuctionOperation {
     // ─────────────────────────────────────────────────────────
 
     /// Place a bid (UIC operation)
+    /// - max_price_per_unit: Reject the bid if the AAC price is above this limit
+    /// - deadline: Reject the bid if the AAC processes it after this time
+    /// - fill_policy: Partial (default), AllOrNothing or MinimumFill(n)
+    /// - delivery: Where refunds are sent - Aac or UserChain (defaults to where payment came from)
+    /// - payment_method: AacBalance (default), Deposit, or UserChain(asset) - the latter
+    ///   escrows max_price_per_unit × quantity, converted at payment_rate, from the user's
+    ///   balance on this chain
+    /// - payment_rate: The auction's rate for the UserChain asset, from its accepted_assets
+    ///   (default 1:1, the auction's own payment_asset); a lower rate under-escrows and the
+    ///   AAC rejects the bid
+    /// - payment_asset: One of the auction's accepted assets (defaults to its payment_asset);
+    ///   prices are converted at the auction's fixed rate for that asset
+    /// - recipient: Deliver the allocation to this account instead (refunds still go to the payer)
     Buy {
         auction_id: u64,
         quantity: u64,
+        max_price_per_unit: Option<Amount>,
+        deadline: Option<Timestamp>,
+        fill_policy: Option<FillPolicy>,
+        delivery: Option<SettlementDelivery>,
+        payment_method: Option<PaymentMethod>,
+        payment_asset: Option<PaymentAsset>,
+        recipient: Option<Account>,
+        payment_rate: Option<Amount>,
+    },
+
+    /// Place a bid on behalf of `beneficiary` (UIC operation)
+    /// Payment is drawn on the AAC with TransferFrom against the beneficiary's allowance
+    /// to the auction application; the allocation and refund belong to the beneficiary.
+    /// The signer must be the beneficiary or a bidder it authorised with AuthorizeBidder.
+    /// Either chain can claim: this one, or the beneficiary's own chain.
+    BuyFor {
+        auction_id: u64,
+        quantity: u64,
+        beneficiary: AccountOwner,
+        max_price_per_unit: Amount,
+        deadline: Option<Timestamp>,
+    },
+
+    /// Buy from several auctions atomically (UIC operation)
+    /// Every leg must fill in full at or below its max price, otherwise nothing is charged.
+    /// - payment_method: AacBalance (default) or Deposit; each leg pays in its auction's payment_asset
+    BuyBasket {
+        legs: Vec<BasketLeg>,
+        payment_method: Option<PaymentMethod>,
     },
 
     /// Subscribe to AAC events for live updates
@@ -68,7 +189,71 @@ pub enum AuctionOperation {
         aac_chain: ChainId,
     },
 
+    /// Deposit funds into the AAC deposit ledger for future bids
+    /// On AAC: from the signer's AAC balance. On UIC: from the signer's balance on that chain.
+    Deposit {
+        payment_asset: PaymentAsset,
+        amount: Amount,
+    },
+
+    /// Withdraw funds from the AAC deposit ledger to the signer's account on the calling chain
+    Withdraw {
+        payment_asset: PaymentAsset,
+        amount: Amount,
+    },
+
+    /// Let `bidder` place BuyFor bids paid from the signer's allowance (either chain)
+    AuthorizeBidder {
+        bidder: AccountOwner,
+    },
+
+    /// Withdraw a bidder's BuyFor authorisation (either chain)
+    RevokeBidder {
+        bidder: AccountOwner,
+    },
+
+    /// Open a pooled group bid for an auction (either chain; the pool lives on the AAC)
+    /// Participants contribute funds; the organiser then places one combined bid
+    /// for target_quantity at up to max_price_per_unit.
+    OpenPool {
+        auction_id: u64,
+        target_quantity: u64,
+        max_price_per_unit: Amount,
+        payment_asset: Option<PaymentAsset>,
+    },
+
+    /// Contribute funds to an open pool (from the signer's balance on the calling chain)
+    ContributeToPool {
+        pool_id: u64,
+        payment_asset: PaymentAsset,
+        amount: Amount,
+    },
+
+    /// Place the pool's combined bid (organiser only)
+    PlacePoolBid {
+        pool_id: u64,
+    },
+
+    /// Cancel an open pool so contributors can reclaim their funds (organiser only)
+    CancelPool {
+        pool_id: u64,
+    },
+
+    /// Claim the signer's pro rata share of a pool's allocation and refund
+    /// If the pool never placed a bid, the full contribution is returned.
+    ClaimPoolShare {
+        pool_id: u64,
+    },
+
+    /// Claim settlement for several settled auctions in one message (UIC operation)
+    /// - auction_ids: Auctions to claim (defaults to every local commitment without a settlement)
+    /// Refunds are combined into one transfer per payment asset.
+    ClaimAll {
+        auction_ids: Option<Vec<u64>>,
+    },
+
     /// Claim settlement for a settled auction (UIC operation)
+    /// Also delivers units others bought as gifts for the signer on this chain.
     ClaimSettlement {
         auction_id: u64,
     },
@@ -87,6 +272,15 @@ pub enum AuctionResponse {
         auction_id: u64,
         quantity: u64,
     },
+
+    PoolOpened {
+        pool_id: u64,
+    },
+
+    Migrated {
+        entries: u64,
+        schema_version: u32,
+    },
 }
 
 /// Application parameters
@@ -4,13 +4,17 @@ mod state;
 
 use async_graphql::{EmptySubscription, Object, Request, Response, Schema, SimpleObject};
 use linera_sdk::graphql::GraphQLMutationRoot;
-use linera_sdk::linera_base_types::{Amount, ChainId, WithServiceAbi};
+use linera_sdk::linera_base_types::{AccountOwner, Amount, ChainId, WithServiceAbi};
 use linera_sdk::views::View;
 use linera_sdk::{Service, ServiceRuntime};
 use auction::AuctionAbi;
-use shared::types::{AuctionId, SettlementResult, UserCommitment, BidRecord};
+use shared::types::{
+    AuctionId, BidRecord, MigrationStatus, PaymentAsset, PaymentTokenInfo, PriceClock, SettlementResult,
+    UnclaimedPolicy, UserCommitment,
+};
+use shared::utils::asset_settlements;
 use std::sync::Arc;
-use self::state::{AuctionState, AuctionData};
+use self::state::{AuctionState, AuctionData, BidderPosition, PoolData, SCHEMA_VERSION};
 
 #[derive(SimpleObject)]
 struct AuctionCommitment {
@@ -53,6 +57,7 @@ impl Service for AuctionService {
         let schema = Schema::build(
             QueryRoot {
                 state: self.state.clone(),
+                runtime: self.runtime.clone(),
             },
             auction::AuctionOperation::mutation_root(self.runtime.clone()),
             EmptySubscription,
@@ -65,6 +70,7 @@ impl Service for AuctionService {
 
 struct QueryRoot {
     state: Arc<AuctionState>,
+    runtime: Arc<ServiceRuntime<AuctionService>>,
 }
 
 #[Object]
@@ -83,9 +89,27 @@ impl QueryRoot {
             .map_err(|e| e.to_string())?
             .ok_or_else(|| "Auction not found".to_string())?;
 
-        // Return the stored current_price
-        // This is updated by the contract during operations (bids, price updates, etc.)
-        Ok(auction.current_price)
+        // Use shared utility functions with the auction's clock source
+        let price = match auction.params.price_clock {
+            PriceClock::WallClock => shared::calculate_current_price(
+                auction.params.start_price,
+                auction.params.floor_price,
+                auction.params.price_decay_amount,
+                auction.params.price_decay_interval,
+                auction.params.start_time,
+                self.runtime.system_time(),
+            ),
+            PriceClock::BlockHeight { start_height } => shared::calculate_price_at_height(
+                auction.params.start_price,
+                auction.params.floor_price,
+                auction.params.price_decay_amount,
+                auction.params.price_decay_interval,
+                start_height,
+                self.runtime.next_block_height(),
+            ),
+        };
+
+        Ok(price)
     }
 
     /// Get auction info (AAC only)
@@ -106,10 +130,12 @@ impl QueryRoot {
 
     /// Get claimable settlement for a user (AAC only)
     /// Returns None if auction not settled or user has no unclaimed bids
+    /// Pass `owner` to count only that owner's bids (what their ClaimSettlement would settle)
     async fn claimable_settlement(
         &self,
         auction_id: AuctionId,
         user_chain: ChainId,
+        owner: Option<AccountOwner>,
     ) -> Result<Option<UserCommitment>, String> {
         // Get auction
         let auction = self
@@ -129,45 +155,180 @@ impl QueryRoot {
             .clearing_price
             .ok_or_else(|| "Clearing price not set".to_string())?;
 
-        // O(1) lookup: Get all unclaimed bids for this user and auction
-        let user_bids = self
-            .state
-            .user_auction_bids
-            .get(&(user_chain, auction_id))
-            .await
-            .map_err(|e| e.to_string())?
-            .unwrap_or_default();
-
-        let mut total_quantity = 0u64;
-        let mut total_paid = Amount::ZERO;
-
-        for bid in user_bids {
-            if !bid.claimed {
-                total_quantity += bid.quantity;
-                total_paid = total_paid.saturating_add(bid.amount_paid);
+        // Unclaimed positions for this chain (just the owner's, if given)
+        let bidders: Vec<AccountOwner> = match owner {
+            Some(owner) => vec![owner],
+            None => self
+                .state
+                .bidder_positions
+                .get(&auction_id)
+                .await
+                .map_err(|e| e.to_string())?
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|(chain_id, _)| *chain_id == user_chain)
+                .map(|(_, bidder)| bidder)
+                .collect(),
+        };
+
+        let mut paid = Vec::new();
+        let mut gifted_quantity = 0;
+        for bidder in bidders {
+            let position = self
+                .state
+                .positions
+                .get(&(user_chain, auction_id, bidder))
+                .await
+                .map_err(|e| e.to_string())?;
+            if let Some(position) = position.filter(|position| !position.claimed) {
+                paid.extend(position.paid);
+                gifted_quantity += position
+                    .gifts
+                    .iter()
+                    .map(|gift| gift.quantity)
+                    .sum::<u64>();
             }
         }
 
+        // Calculate per-asset settlement from unclaimed positions
+        let payments = asset_settlements(&auction.params, clearing_price, &paid);
+
         // No unclaimed bids
-        if total_quantity == 0 {
+        if payments.is_empty() {
             return Ok(None);
         }
 
-        // Calculate settlement
+        let total_quantity: u64 = payments.iter().map(|payment| payment.quantity).sum();
         let total_cost = clearing_price.saturating_mul(total_quantity as u128);
-        let refund = total_paid.saturating_sub(total_cost);
 
         Ok(Some(UserCommitment {
             total_quantity,
             settlement: Some(SettlementResult {
-                allocated_quantity: total_quantity,
+                allocated_quantity: total_quantity - gifted_quantity,
                 clearing_price,
                 total_cost,
-                refund,
+                payments,
+                gifted_quantity,
+                gifted_from: None,
             }),
+            gifts_received: Vec::new(),
+            pool_shares: Vec::new(),
         }))
     }
 
+    /// Get the quantity an owner has bought in an auction, across all their chains (AAC only)
+    async fn owner_position(
+        &self,
+        owner: AccountOwner,
+        auction_id: AuctionId,
+    ) -> Result<u64, String> {
+        Ok(self
+            .state
+            .owner_totals
+            .get(&(owner, auction_id))
+            .await
+            .map_err(|e| e.to_string())?
+            .unwrap_or(0))
+    }
+
+    /// Get an owner's aggregate position in an auction from one chain (AAC only)
+    async fn bidder_position(
+        &self,
+        user_chain: ChainId,
+        auction_id: AuctionId,
+        owner: AccountOwner,
+    ) -> Result<Option<BidderPosition>, String> {
+        self.state
+            .positions
+            .get(&(user_chain, auction_id, owner))
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// List approved fungible payment tokens (AAC only)
+    async fn payment_tokens(&self) -> Result<Vec<PaymentTokenInfo>, String> {
+        let indices = self
+            .state
+            .payment_tokens
+            .indices()
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut result = Vec::new();
+
+        for token_app in indices {
+            if let Some(info) = self
+                .state
+                .payment_tokens
+                .get(&token_app)
+                .await
+                .map_err(|e| e.to_string())?
+            {
+                result.push(info);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Get the payment token registry admin (AAC only)
+    async fn registry_admin(&self) -> Option<AccountOwner> {
+        *self.state.admin.get()
+    }
+
+    /// List chains whose indexer may confirm archives (AAC only)
+    async fn indexers(&self) -> Result<Vec<ChainId>, String> {
+        self.state.indexers.indices().await.map_err(|e| e.to_string())
+    }
+
+    /// Get what SweepUnclaimed does with unclaimed refunds (AAC only)
+    async fn unclaimed_policy(&self) -> UnclaimedPolicy {
+        *self.state.unclaimed_policy.get()
+    }
+
+    /// Get the stored schema version and progress of a pending Migrate (AAC only)
+    async fn migration_status(&self) -> MigrationStatus {
+        let schema_version = *self.state.schema_version.get();
+        MigrationStatus {
+            schema_version,
+            target_version: SCHEMA_VERSION,
+            migrated: *self.state.migration_cursor.get(),
+            complete: schema_version >= SCHEMA_VERSION,
+        }
+    }
+
+    /// Get an owner's deposit ledger balance for a payment asset (AAC only)
+    async fn deposit_balance(
+        &self,
+        owner: AccountOwner,
+        payment_asset: PaymentAsset,
+    ) -> Result<Amount, String> {
+        Ok(self
+            .state
+            .deposits
+            .get(&(owner, payment_asset))
+            .await
+            .map_err(|e| e.to_string())?
+            .unwrap_or_default())
+    }
+
+    /// Whether `bidder` may place BuyFor bids for `beneficiary` (AAC only)
+    async fn is_authorised_bidder(
+        &self,
+        beneficiary: AccountOwner,
+        bidder: AccountOwner,
+    ) -> Result<bool, String> {
+        self.state
+            .authorised_bidders
+            .contains_key(&(beneficiary, bidder))
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Get a pooled group bid with its contributions (AAC only)
+    async fn pool(&self, pool_id: u64) -> Result<Option<PoolData>, String> {
+        self.state.pools.get(&pool_id).await.map_err(|e| e.to_string())
+    }
+
     // ─────────────────────────────────────────────────────────
     // UIC Chain Queries (available on UIC chains)
     // ─────────────────────────────────────────────────────────
@@ -177,11 +338,23 @@ impl QueryRoot {
         &self,
         auction_id: AuctionId,
     ) -> Result<Option<UserCommitment>, String> {
-        self.state
+        if let Some(commitment) = self
+            .state
             .my_commitments
             .get(&auction_id)
             .await
-            .map_err(|e| e.to_string())
+            .map_err(|e| e.to_string())?
+        {
+            return Ok(Some(commitment));
+        }
+        // Not migrated yet
+        Ok(self
+            .state
+            .legacy_commitments
+            .get(&auction_id)
+            .await
+            .map_err(|e| e.to_string())?
+            .map(|legacy| legacy.into_commitment()))
     }
 
     /// Get all user's commitments (UIC only)
@@ -209,6 +382,28 @@ impl QueryRoot {
             }
         }
 
+        // Commitments Migrate has not reached yet
+        let legacy_indices = self
+            .state
+            .legacy_commitments
+            .indices()
+            .await
+            .map_err(|e| e.to_string())?;
+        for auction_id in legacy_indices {
+            if let Some(legacy) = self
+                .state
+                .legacy_commitments
+                .get(&auction_id)
+                .await
+                .map_err(|e| e.to_string())?
+            {
+                result.push(AuctionCommitment {
+                    auction_id,
+                    commitment: legacy.into_commitment(),
+                });
+            }
+        }
+
         Ok(result)
     }
 
@@ -309,13 +504,12 @@ impl QueryRoot {
     }
 
     /// Get bid history for a specific auction in chronological order (oldest first)
-    /// Uses the total number of bids from auction.total_bids for reverse iteration
     /// - auction_id: The auction to get bids for
     /// - offset: Skip the first N oldest bids (default: 0)
     /// - limit: Return at most N bids (default: 50)
     ///
-    /// Note: This flattens all user_auction_bids entries for the given auction.
-    /// This is a temporary workaround - Indexer will provide optimized bid_history queries in the future.
+    /// Reads a page of the auction's append-only bid log. `claimed` reflects acceptance time;
+    /// see claimableSettlement for what is still unclaimed.
     async fn bid_history(
         &self,
         auction_id: AuctionId,
@@ -334,41 +528,22 @@ impl QueryRoot {
         let offset = offset.unwrap_or(0);
         let limit = limit.unwrap_or(50);
 
-        // Collect all bids for this auction from user_auction_bids map
-        // Note: This iterates all entries in the map and filters by auction_id
-        // For large maps, this is O(n). Indexer will provide O(1) lookup in the future.
-        let indices = self
+        let Some(bid_log) = self
             .state
-            .user_auction_bids
-            .indices()
+            .bid_log
+            .try_load_entry(&auction_id)
             .await
-            .map_err(|e| e.to_string())?;
-
-        let mut all_bids = Vec::new();
-
-        for (user_chain, aid) in indices {
-            if aid == auction_id {
-                if let Some(user_bids) = self
-                    .state
-                    .user_auction_bids
-                    .get(&(user_chain, aid))
-                    .await
-                    .map_err(|e| e.to_string())?
-                {
-                    all_bids.extend(user_bids);
-                }
-            }
-        }
-
-        // Sort by bid_id (chronological order)
-        all_bids.sort_by_key(|bid| bid.bid_id);
+            .map_err(|e| e.to_string())?
+        else {
+            return Ok(Vec::new()); // No bids yet, or pruned
+        };
 
         // Apply pagination
-        let result: Vec<BidRecord> = all_bids
-            .into_iter()
-            .skip(offset)
-            .take(limit)
-            .collect();
+        let end = offset.saturating_add(limit).min(bid_log.count());
+        if offset >= end {
+            return Ok(Vec::new());
+        }
+        let result = bid_log.read(offset..end).await.map_err(|e| e.to_string())?;
 
         Ok(result)
     }
@@ -1,24 +1,43 @@
 use async_graphql::{SimpleObject};
-use linera_sdk::linera_base_types::{Amount, ChainId, Timestamp};
-use linera_sdk::views::{linera_views, MapView, RegisterView, RootView, ViewStorageContext};
-use shared::types::{AuctionId, AuctionParams, AuctionStatus, BidRecord, UserCommitment};
+use linera_sdk::linera_base_types::{
+    Account, AccountOwner, Amount, ApplicationId, BcsHashable, ChainId, CryptoHash, Timestamp,
+};
+use linera_sdk::views::{
+    linera_views, CollectionView, LogView, MapView, RegisterView, RootView, ViewStorageContext,
+};
+use shared::types::{
+    AssetPaid, AuctionId, AuctionParams, AuctionStatus, BidRecord, FillPolicy, LegacyBidRecord,
+    PaymentAsset, PaymentTokenInfo, PoolStatus, PriceClock, SettlementDelivery, SettlementResult,
+    UnclaimedPolicy, UserCommitment, DEFAULT_CLAIM_WINDOW,
+};
+
+/// Schema version written by this code; `Migrate` upgrades older state to it
+/// - 1: auctions in the current AuctionData layout (moved out of legacy_auctions)
+/// - 2: bids held as positions and a per-auction bid log (replaces user_auction_bids)
+/// - 3: UIC commitments in the current UserCommitment layout (moved out of legacy_commitments)
+pub const SCHEMA_VERSION: u32 = 3;
 
 /// Unified state for the Auction Application
 /// Different chain types use different subsets of this state:
-/// - AAC Chain: Uses auctions, user_auction_bids, user_totals (auction authority data)
+/// - AAC Chain: Uses auctions, positions, bid_log, bidder_positions, user_totals, owner_totals, deposits, payment_tokens, indexers (auction authority data)
 /// - UIC Chains: Uses my_commitments (user-specific data)
+///
+/// Views are stored under their field position, so the first six fields keep the
+/// positions (and record layouts) of the original release; new fields are appended.
 #[derive(RootView)]
 #[view(context = ViewStorageContext)]
 pub struct AuctionState {
     // ─────────────────────────────────────────────────────────
-    // AAC Chain State (only used on AAC chain)
+    // Original release layout (read by Migrate)
     // ─────────────────────────────────────────────────────────
 
-    /// Active auctions (AAC only)
-    pub auctions: MapView<AuctionId, AuctionData>,
+    /// Auctions in the original layout (AAC only)
+    /// No longer written; Migrate moves them into auctions (schema version 1).
+    pub legacy_auctions: MapView<AuctionId, LegacyAuctionData>,
 
-    /// Bid records indexed by (user_chain, auction_id) for O(1) lookups (AAC only)
-    pub user_auction_bids: MapView<(ChainId, AuctionId), Vec<BidRecord>>,
+    /// Legacy per-bid vectors by (user_chain, auction_id) (AAC only)
+    /// No longer written; Migrate moves them into positions and bid_log (schema version 2).
+    pub user_auction_bids: MapView<(ChainId, AuctionId), Vec<LegacyBidRecord>>,
 
     /// User totals per auction (AAC only, for quick lookup)
     pub user_totals: MapView<(AuctionId, ChainId), u64>,  // (auction_id, user) → quantity
@@ -29,6 +48,81 @@ pub struct AuctionState {
     /// Next bid ID (AAC only, for generating unique bid IDs)
     pub next_bid_id: RegisterView<u64>,
 
+    /// User's commitments in the original layout (UIC only)
+    /// No longer written; Migrate moves them into my_commitments (schema version 3).
+    pub legacy_commitments: MapView<AuctionId, LegacyUserCommitment>,
+
+    // ─────────────────────────────────────────────────────────
+    // AAC Chain State (only used on AAC chain)
+    // ─────────────────────────────────────────────────────────
+
+    /// Active auctions (AAC only)
+    pub auctions: MapView<AuctionId, AuctionData>,
+
+    /// Index: auctions not yet ended or cancelled (AAC only), so Trigger skips finished ones
+    pub open_auctions: MapView<AuctionId, ()>,
+
+    /// Index: settled auto-distributing auctions with settlements left to push (AAC only)
+    pub pending_distributions: MapView<AuctionId, ()>,
+
+    /// Aggregate position per (user_chain, auction_id, bidder) - all that claims read (AAC only)
+    pub positions: MapView<(ChainId, AuctionId, AccountOwner), BidderPosition>,
+
+    /// Append-only log of accepted bids per auction, in bid order (AAC only)
+    pub bid_log: CollectionView<AuctionId, LogView<BidRecord>>,
+
+    /// Index: auction → position keys, in first-bid order (AAC only)
+    /// Lets per-auction walks (pruning, distribution) skip every other auction's bids
+    pub bidder_positions: MapView<AuctionId, Vec<(ChainId, AccountOwner)>>,
+
+    /// Owner totals per auction (AAC only, positions keyed by the paying owner)
+    pub owner_totals: MapView<(AccountOwner, AuctionId), u64>,  // (owner, auction_id) → quantity
+
+    /// Escrowed bids awaiting submission-time ordering (AAC only, fairness mode)
+    pub pending_bids: MapView<AuctionId, Vec<PendingBid>>,
+
+    /// Deposit ledger: (owner, payment asset) → balance held in escrow (AAC only)
+    pub deposits: MapView<(AccountOwner, PaymentAsset), Amount>,
+
+    /// BuyFor delegations: (beneficiary, bidder) pairs the beneficiary authorised (AAC only)
+    pub authorised_bidders: MapView<(AccountOwner, AccountOwner), ()>,
+
+    /// Index: (beneficiary, auction_id) → chains holding BuyFor positions for it (AAC only)
+    /// Lets the beneficiary claim those positions from its own chain.
+    pub delegated_positions: MapView<(AccountOwner, AuctionId), Vec<ChainId>>,
+
+    /// Index: (gift recipient, auction_id) → payer positions holding gifts for it (AAC only)
+    /// Lets the recipient claim its allocation without waiting for the payer.
+    pub gift_positions: MapView<(Account, AuctionId), Vec<(ChainId, AccountOwner)>>,
+
+    /// Pooled group bids (AAC only)
+    pub pools: MapView<u64, PoolData>,
+
+    /// Next pool ID (AAC only)
+    pub next_pool_id: RegisterView<u64>,
+
+    /// Next basket ID (AAC only)
+    pub next_basket_id: RegisterView<u64>,
+
+    /// Registry admin: the signer that instantiated the application (AAC only)
+    pub admin: RegisterView<Option<AccountOwner>>,
+
+    /// What SweepUnclaimed does with unclaimed refunds (AAC only, set by the admin)
+    pub unclaimed_policy: RegisterView<UnclaimedPolicy>,
+
+    /// Approved fungible payment tokens (AAC only)
+    pub payment_tokens: MapView<ApplicationId, PaymentTokenInfo>,
+
+    /// Chains whose indexer may confirm archives before pruning, with the indexer application
+    /// (AAC only, set by the admin)
+    pub indexers: MapView<ChainId, ApplicationId>,
+
+    /// Schema version of the stored data (0 = written before versioning)
+    pub schema_version: RegisterView<u32>,
+
+    /// Entries migrated so far in the pending migration step
+    pub migration_cursor: RegisterView<u64>,
+
     // ─────────────────────────────────────────────────────────
     // UIC Chain State (only used on UIC chains)
     // ─────────────────────────────────────────────────────────
@@ -49,9 +143,19 @@ pub struct AuctionData {
     pub status: AuctionStatus,
     pub settled_at: Option<Timestamp>,
     pub bids_pruned: bool,
-    // Cached counters to avoid O(n) scans on user_auction_bids
+    // Cached counters to avoid O(n) scans over positions
     pub total_bids: u64,      // Total number of bids placed
     pub total_bidders: u64,   // Total unique users who bid
+    pub sold_by_asset: Vec<AssetQuantity>, // Units sold per payment asset (for proceeds)
+    // Auto-distribution progress (params.auto_distribute)
+    pub distribution_cursor: u64, // Bidder positions already settled, in first-bid order
+    pub distribution_complete: bool,
+    pub sweep_cursor: u64, // Bidder positions already visited by SweepUnclaimed, in first-bid order
+    pub unclaimed_swept: bool, // SweepUnclaimed finished: every bid is claimed and may be pruned
+    pub bid_digest: Option<CryptoHash>, // Hash chain over the bid log (see shared::utils::bid_log_digest)
+    pub digest_start: u64, // Leading bids of the log that bid_digest skips (legacy migrated)
+    pub archive_confirmed: bool, // A registered indexer holds the full bid history (pruning allowed)
+    pub proceeds_claimed: bool, // The creator collected the sale proceeds with ClaimProceeds
 }
 
 impl AuctionData {
@@ -74,8 +178,267 @@ impl AuctionData {
             bids_pruned: false,
             total_bids: 0,
             total_bidders: 0,
+            sold_by_asset: Vec::new(),
+            distribution_cursor: 0,
+            distribution_complete: false,
+            sweep_cursor: 0,
+            unclaimed_swept: false,
+            bid_digest: None,
+            digest_start: 0,
+            archive_confirmed: false,
+            proceeds_claimed: false,
             params,
         }
     }
 }
 
+/// Auction parameters as stored before schema version 1 (frozen: existing rows decode with it)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LegacyAuctionParams {
+    pub item_name: String,
+    pub total_supply: u64,
+    pub start_price: Amount,
+    pub floor_price: Amount,
+    pub price_decay_interval: u64,
+    pub price_decay_amount: Amount,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    pub creator: AccountOwner,
+    pub payment_token_app: ApplicationId,
+}
+
+/// Auction state as stored before schema version 1 (frozen: existing rows decode with it)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LegacyAuctionData {
+    pub params: LegacyAuctionParams,
+    pub current_price: Amount,
+    pub last_price_update: Timestamp,
+    pub total_supply: u64,
+    pub sold: u64,
+    pub clearing_price: Option<Amount>,
+    pub status: AuctionStatus,
+    pub settled_at: Option<Timestamp>,
+    pub bids_pruned: bool,
+    pub total_bids: u64,
+    pub total_bidders: u64,
+}
+
+impl LegacyAuctionData {
+    /// Convert to the current layout: a wall-clock auction paid in its single token,
+    /// with none of the later options enabled
+    pub fn into_auction_data(self) -> AuctionData {
+        let legacy = self.params;
+        let payment_asset = PaymentAsset::Fungible(legacy.payment_token_app);
+        let params = AuctionParams {
+            item_name: legacy.item_name,
+            total_supply: legacy.total_supply,
+            start_price: legacy.start_price,
+            floor_price: legacy.floor_price,
+            price_decay_interval: legacy.price_decay_interval,
+            price_decay_amount: legacy.price_decay_amount,
+            start_time: legacy.start_time,
+            end_time: legacy.end_time,
+            creator: legacy.creator,
+            payment_asset,
+            accepted_assets: Vec::new(),
+            fair_ordering_window: None,
+            price_clock: PriceClock::WallClock,
+            end_conditions: Vec::new(),
+            auto_distribute: false,
+            claim_window: DEFAULT_CLAIM_WINDOW,
+        };
+        let sold_by_asset = if self.sold > 0 {
+            vec![AssetQuantity {
+                payment_asset,
+                quantity: self.sold,
+            }]
+        } else {
+            Vec::new()
+        };
+
+        AuctionData {
+            params,
+            current_price: self.current_price,
+            last_price_update: self.last_price_update,
+            total_supply: self.total_supply,
+            sold: self.sold,
+            clearing_price: self.clearing_price,
+            status: self.status,
+            settled_at: self.settled_at,
+            bids_pruned: self.bids_pruned,
+            total_bids: self.total_bids,
+            total_bidders: self.total_bidders,
+            sold_by_asset,
+            distribution_cursor: 0,
+            distribution_complete: false,
+            sweep_cursor: 0,
+            unclaimed_swept: false,
+            bid_digest: None,
+            digest_start: self.total_bids,
+            archive_confirmed: false,
+            proceeds_claimed: false,
+        }
+    }
+}
+
+/// Settlement result as stored before schema version 3 (frozen: existing rows decode with it)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LegacySettlementResult {
+    pub allocated_quantity: u64,
+    pub clearing_price: Amount,
+    pub total_cost: Amount,
+    pub refund: Amount,
+}
+
+/// User commitment as stored before schema version 3 (frozen: existing rows decode with it)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LegacyUserCommitment {
+    pub total_quantity: u64,
+    pub settlement: Option<LegacySettlementResult>,
+}
+
+impl LegacyUserCommitment {
+    /// Convert to the current layout
+    /// The user chain never knew the payment token, so the per-asset breakdown stays empty;
+    /// the refund itself was paid out when the claim was made.
+    pub fn into_commitment(self) -> UserCommitment {
+        UserCommitment {
+            total_quantity: self.total_quantity,
+            settlement: self.settlement.map(|settlement| SettlementResult {
+                allocated_quantity: settlement.allocated_quantity,
+                clearing_price: settlement.clearing_price,
+                total_cost: settlement.total_cost,
+                payments: Vec::new(),
+                gifted_quantity: 0,
+                gifted_from: None,
+            }),
+            gifts_received: Vec::new(),
+            pool_shares: Vec::new(),
+        }
+    }
+}
+
+/// One owner's bids on an auction from one chain, aggregated (stored on AAC chain)
+/// Updated in place on every bid, so writes stay the same size however often the owner bids.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, SimpleObject)]
+pub struct BidderPosition {
+    pub quantity: u64, // Units across all bids, including gifts
+    pub paid: Vec<AssetPaid>, // Units and escrow per payment asset
+    pub gifts: Vec<GiftedQuantity>, // Units allocated to gift recipients
+    pub delivery: SettlementDelivery, // Where refunds go (latest bid's preference)
+    pub bid_count: u64,
+    pub claimed: bool,
+}
+
+/// Units a position paid for on behalf of one gift recipient
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, SimpleObject)]
+pub struct GiftedQuantity {
+    pub recipient: Account,
+    pub quantity: u64,
+    pub delivered: bool, // SettlementResult already sent to the recipient
+}
+
+impl BidderPosition {
+    /// Fold an accepted bid into the position
+    pub fn record_bid(&mut self, bid: &BidRecord) {
+        self.quantity += bid.quantity;
+        self.bid_count += 1;
+        self.delivery = bid.delivery;
+
+        match self
+            .paid
+            .iter_mut()
+            .find(|paid| paid.payment_asset == bid.payment_asset)
+        {
+            Some(paid) => {
+                paid.quantity += bid.quantity;
+                paid.amount_paid.saturating_add_assign(bid.amount_paid);
+            }
+            None => self.paid.push(AssetPaid {
+                payment_asset: bid.payment_asset,
+                quantity: bid.quantity,
+                amount_paid: bid.amount_paid,
+            }),
+        }
+
+        if let Some(recipient) = bid.recipient {
+            match self.gifts.iter_mut().find(|gift| gift.recipient == recipient) {
+                Some(gift) => gift.quantity += bid.quantity,
+                None => self.gifts.push(GiftedQuantity {
+                    recipient,
+                    quantity: bid.quantity,
+                    delivered: false,
+                }),
+            }
+        }
+    }
+}
+
+/// Bid queued in fairness mode (stored on AAC chain)
+/// Payment for the full requested quantity is already in escrow.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PendingBid {
+    pub user_chain: ChainId,
+    pub bidder: AccountOwner,
+    pub quantity: u64,
+    pub fill_policy: FillPolicy,
+    pub price_per_unit: Amount, // Price at submission time
+    pub escrowed: Amount, // price_per_unit × quantity, converted to payment_asset
+    pub submitted_at: Timestamp,
+    pub delivery: SettlementDelivery,
+    pub payment_asset: PaymentAsset, // Asset `escrowed` is denominated in
+    pub recipient: Option<Account>, // Gift recipient of the allocation
+}
+
+/// Pooled group bid (stored on AAC chain)
+/// Contributions sit in the app escrow until the pool's share is claimed.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, SimpleObject)]
+pub struct PoolData {
+    pub pool_id: u64,
+    pub auction_id: AuctionId,
+    pub organiser: AccountOwner,
+    pub target_quantity: u64,
+    pub max_price_per_unit: Amount,
+    pub payment_asset: PaymentAsset,
+    pub status: PoolStatus,
+    pub total_contributed: Amount,
+    pub contributions: Vec<PoolContribution>,
+    pub bid_id: Option<u64>, // Set once the combined bid is accepted
+    pub allocated_quantity: u64, // Units the combined bid was filled for
+    pub shares_computed: bool, // Pro rata shares fixed (after the auction settles)
+}
+
+/// Seed of the owner a pool's combined bid is recorded under
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PoolBidder {
+    application_id: ApplicationId,
+    pool_id: u64,
+}
+
+impl BcsHashable<'_> for PoolBidder {}
+
+/// Owner a pool's combined bid is recorded under: one position per pool, never a signer
+pub fn pool_bidder(application_id: ApplicationId, pool_id: u64) -> AccountOwner {
+    AccountOwner::Address32(CryptoHash::new(&PoolBidder {
+        application_id,
+        pool_id,
+    }))
+}
+
+/// One contributor's stake in a pool
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, SimpleObject)]
+pub struct PoolContribution {
+    pub contributor: AccountOwner,
+    pub chain_id: ChainId, // Chain the latest contribution came from; refunds go here
+    pub amount: Amount,
+    pub allocated_quantity: u64, // Pro rata share, set when shares are computed
+    pub refund: Amount, // Pro rata refund, set when shares are computed
+    pub claimed: bool,
+}
+
+/// Units sold in one payment asset (stored on AAC chain)
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, SimpleObject)]
+pub struct AssetQuantity {
+    pub payment_asset: PaymentAsset,
+    pub quantity: u64,
+}
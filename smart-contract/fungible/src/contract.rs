@@ -11,7 +11,7 @@ use linera_sdk::{
     Contract, ContractRuntime,
 };
 
-use self::state::FungibleTokenState;
+use self::state::{FungibleTokenState, SCHEMA_VERSION};
 
 pub struct FungibleTokenContract {
     state: FungibleTokenState,
@@ -40,6 +40,8 @@ impl Contract for FungibleTokenContract {
     async fn instantiate(&mut self, _argument: Self::InstantiationArgument) {
         // Validate that the application parameters were configured correctly
         let _ = self.runtime.application_parameters();
+        self.state.admin.set(self.runtime.authenticated_signer());
+        self.state.schema_version.set(SCHEMA_VERSION);
     }
 
     async fn execute_operation(&mut self, operation: Self::Operation) -> Self::Response {
@@ -119,6 +121,12 @@ impl Contract for FungibleTokenContract {
                 self.claim(source_account, amount, target_account).await;
                 FungibleResponse::Ok
             }
+
+            FungibleOperation::Migrate => {
+                self.assert_admin();
+                self.migrate();
+                FungibleResponse::Migrated(SCHEMA_VERSION)
+            }
         }
     }
 
@@ -157,6 +165,32 @@ impl Contract for FungibleTokenContract {
 }
 
 impl FungibleTokenContract {
+    /// Checks the signer is this chain's token admin
+    /// Chains upgraded from before the admin existed have none: a chain owner takes the role.
+    fn assert_admin(&mut self) {
+        let signer = self
+            .runtime
+            .authenticated_signer()
+            .expect("Admin operations must be authenticated");
+
+        match *self.state.admin.get() {
+            Some(admin) => assert_eq!(signer, admin, "Only the admin can migrate the token state"),
+            None => {
+                assert!(
+                    self.runtime.chain_ownership().verify_owner(&signer),
+                    "Only an owner of this chain can take the unset admin role"
+                );
+                self.state.admin.set(Some(signer));
+            }
+        }
+    }
+
+    /// Records that this chain's token state is at the current schema version
+    /// Accounts and allowances have kept their original layout, so no entry is rewritten.
+    fn migrate(&mut self) {
+        self.state.schema_version.set(SCHEMA_VERSION);
+    }
+
     /// Claims tokens from a source account and transfers to target account
     async fn claim(&mut self, source_account: Account, amount: Amount, target_account: Account) {
         if source_account.chain_id == self.runtime.chain_id() {
@@ -83,6 +83,11 @@ pub enum FungibleOperation {
         amount: Amount,
         target_account: Account,
     },
+
+    /// Record that this chain's token state is at the current schema version
+    /// (admin only; without an admin, a chain owner becomes it). The token has no stored
+    /// data to migrate: accounts and allowances keep their original layout.
+    Migrate,
 }
 
 /// Responses from operations
@@ -92,6 +97,7 @@ pub enum FungibleResponse {
     Balance(Amount),
     TickerSymbol(String),
     TokenName(String),
+    Migrated(u32), // Schema version now recorded
 }
 
 /// Messages for cross-chain communication
@@ -12,8 +12,9 @@ use linera_sdk::{
     views::{MapView, View},
     Service, ServiceRuntime,
 };
+use shared::types::MigrationStatus;
 
-use self::state::FungibleTokenState;
+use self::state::{FungibleTokenState, SCHEMA_VERSION};
 
 #[derive(Clone)]
 pub struct FungibleTokenService {
@@ -72,4 +73,15 @@ impl FungibleTokenService {
     async fn token_name(&self) -> Result<String, async_graphql::Error> {
         Ok(self.runtime.application_parameters().name)
     }
+
+    /// Get the stored schema version (Migrate has nothing to rewrite for the token)
+    async fn migration_status(&self) -> MigrationStatus {
+        let schema_version = *self.state.schema_version.get();
+        MigrationStatus {
+            schema_version,
+            target_version: SCHEMA_VERSION,
+            migrated: 0,
+            complete: schema_version >= SCHEMA_VERSION,
+        }
+    }
 }
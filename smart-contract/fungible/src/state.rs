@@ -1,15 +1,24 @@
 use fungible::OwnerSpender;
 use linera_sdk::{
     linera_base_types::{AccountOwner, Amount},
-    views::{linera_views, MapView, RootView, ViewStorageContext},
+    views::{linera_views, MapView, RegisterView, RootView, ViewStorageContext},
 };
 
+/// Schema version written by this code; `Migrate` records it on older chains
+/// - 1: version marker introduced (accounts and allowances keep their original layout)
+pub const SCHEMA_VERSION: u32 = 1;
+
 /// The application state for the fungible token
 #[derive(RootView)]
 #[view(context = ViewStorageContext)]
 pub struct FungibleTokenState {
     pub accounts: MapView<AccountOwner, Amount>,
     pub allowances: MapView<OwnerSpender, Amount>,
+    /// The signer that instantiated the token, or on chains without one the chain owner
+    /// that first ran Migrate (may run Migrate)
+    pub admin: RegisterView<Option<AccountOwner>>,
+    /// Schema version of the stored data (0 = written before versioning)
+    pub schema_version: RegisterView<u32>,
 }
 
 impl FungibleTokenState {
@@ -2,13 +2,15 @@
 
 mod state;
 
-use self::state::IndexerState;
+use self::state::{IndexerState, SCHEMA_VERSION};
+use auction::{AuctionAbi, AuctionOperation};
 use indexer::{IndexerAbi, IndexerOperation, IndexerParameters, IndexerResponse};
 use linera_sdk::linera_base_types::{StreamUpdate, WithContractAbi};
 use linera_sdk::views::{RootView, View};
 use linera_sdk::{Contract, ContractRuntime};
 use shared::events::{AuctionEvent, AUCTION_STREAM};
-use shared::types::{AuctionStatus, AuctionSummary, BidRecord};
+use shared::types::{AuctionStatus, AuctionSummary, BasketRecord, BidRecord};
+use shared::utils::bid_log_digest;
 
 pub struct IndexerContract {
     state: IndexerState,
@@ -36,6 +38,8 @@ impl Contract for IndexerContract {
 
     async fn instantiate(&mut self, _argument: Self::InstantiationArgument) {
         // Indexer is ready to be initialized via the Initialize operation
+        self.state.admin.set(self.runtime.authenticated_signer());
+        self.state.schema_version.set(SCHEMA_VERSION);
     }
 
     async fn execute_operation(&mut self, operation: Self::Operation) -> Self::Response {
@@ -68,6 +72,21 @@ impl Contract for IndexerContract {
                     auction_app,
                 }
             }
+
+            IndexerOperation::Migrate { max_entries } => {
+                self.assert_admin();
+                let entries = self.migrate(max_entries as usize).await;
+                IndexerResponse::Migrated {
+                    entries: entries as u64,
+                    schema_version: *self.state.schema_version.get(),
+                }
+            }
+
+            IndexerOperation::ConfirmArchive { auction_id } => {
+                self.migrate_auction(auction_id).await;
+                self.confirm_archive(auction_id).await;
+                IndexerResponse::Ok
+            }
         }
     }
 
@@ -96,6 +115,117 @@ impl Contract for IndexerContract {
 }
 
 impl IndexerContract {
+    /// Check the signer is the indexer admin
+    /// Indexers upgraded from before the admin existed have none: a chain owner takes the role.
+    fn assert_admin(&mut self) {
+        let signer = self
+            .runtime
+            .authenticated_signer()
+            .expect("Admin operations must be authenticated");
+
+        match *self.state.admin.get() {
+            Some(admin) => assert_eq!(signer, admin, "Only the admin can migrate the indexer"),
+            None => {
+                assert!(
+                    self.runtime.chain_ownership().verify_owner(&signer),
+                    "Only an owner of the indexer chain can take the unset admin role"
+                );
+                self.state.admin.set(Some(signer));
+            }
+        }
+    }
+
+    /// Tell the AAC this indexer holds a settled auction's full bid history, so it may prune
+    /// Sent through the auction application on this chain; the AAC checks the count and digest.
+    async fn confirm_archive(&mut self, auction_id: u64) {
+        let Some(subscription) = self.state.subscription.get().clone() else {
+            return;
+        };
+        // No history means the indexer started after the auction was created
+        let Some(history) = self.state.bid_history.get(&auction_id).await.unwrap() else {
+            return;
+        };
+        let digest_start = self
+            .state
+            .auction_summaries
+            .get(&auction_id)
+            .await
+            .unwrap()
+            .map_or(0, |summary| summary.digest_start);
+
+        let operation = AuctionOperation::ConfirmArchive {
+            auction_id,
+            bid_count: history.len() as u64,
+            digest: bid_log_digest(history.iter().skip(digest_start as usize)),
+        };
+        self.runtime.call_application(
+            true,
+            subscription.auction_app.with_abi::<AuctionAbi>(),
+            &operation,
+        );
+    }
+
+    /// Move up to `max_entries` auctions' summaries and bid histories out of the
+    /// original-layout maps, returning how many auctions were moved
+    /// Once none are left the indexer is at the current schema version.
+    async fn migrate(&mut self, max_entries: usize) -> usize {
+        assert!(max_entries > 0, "max_entries must be greater than 0");
+        if *self.state.schema_version.get() >= SCHEMA_VERSION {
+            return 0;
+        }
+
+        let mut batch = Vec::new();
+        self.state
+            .legacy_summaries
+            .for_each_index_while(|auction_id| {
+                batch.push(auction_id);
+                Ok(batch.len() < max_entries)
+            })
+            .await
+            .unwrap();
+
+        for auction_id in &batch {
+            self.migrate_auction(*auction_id).await;
+        }
+
+        if batch.len() < max_entries {
+            self.state.schema_version.set(SCHEMA_VERSION);
+            self.state.migration_cursor.set(0);
+        } else {
+            *self.state.migration_cursor.get_mut() += batch.len() as u64;
+        }
+        batch.len()
+    }
+
+    /// Move one auction's summary and bid history into the current layout, if still legacy
+    /// Events for an auction run this first, so they never update a summary Migrate
+    /// has not reached yet.
+    async fn migrate_auction(&mut self, auction_id: u64) {
+        if *self.state.schema_version.get() >= SCHEMA_VERSION {
+            return;
+        }
+        let Some(legacy) = self.state.legacy_summaries.get(&auction_id).await.unwrap() else {
+            return;
+        };
+        let summary = legacy.into_summary();
+
+        let history: Vec<BidRecord> = self
+            .state
+            .legacy_bid_history
+            .get(&auction_id)
+            .await
+            .unwrap()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|bid| bid.into_bid(summary.payment_asset))
+            .collect();
+
+        self.state.bid_history.insert(&auction_id, history).unwrap();
+        self.state.auction_summaries.insert(&auction_id, summary).unwrap();
+        self.state.legacy_bid_history.remove(&auction_id).unwrap();
+        self.state.legacy_summaries.remove(&auction_id).unwrap();
+    }
+
     async fn handle_event(&mut self, event: AuctionEvent) {
         match event {
             AuctionEvent::ApplicationInitialized { aac_chain: _ } => {
@@ -113,7 +243,10 @@ impl IndexerContract {
                 start_time,
                 end_time,
                 creator,
-                payment_token_app,
+                payment_asset,
+                accepted_assets,
+                price_clock,
+                block_height,
             } => {
                 // Determine initial status: Scheduled if start_time is in the future, otherwise Active
                 let now = self.runtime.system_time();
@@ -135,7 +268,9 @@ impl IndexerContract {
                     start_time,
                     end_time,
                     creator,
-                    payment_token_app,
+                    payment_asset,
+                    accepted_assets,
+                    price_clock,
                     // Derived state
                     current_price: start_price,
                     sold: 0,
@@ -143,6 +278,9 @@ impl IndexerContract {
                     status: initial_status,
                     total_bids: 0,
                     total_bidders: 0,
+                    last_block_height: block_height,
+                    bid_digest: None,
+                    digest_start: 0,
                 };
 
                 self.state
@@ -171,11 +309,19 @@ impl IndexerContract {
                 auction_id,
                 bid_id,
                 user_chain,
+                bidder,
+                requested_quantity: _,
                 quantity,
                 amount_paid,
                 total_sold,
                 remaining: _,
+                block_height,
+                delivery,
+                payment_asset,
+                recipient,
             } => {
+                self.migrate_auction(auction_id).await;
+
                 // Update summary
                 if let Some(mut summary) = self
                     .state
@@ -186,23 +332,56 @@ impl IndexerContract {
                 {
                     summary.sold = total_sold;
                     summary.total_bids += 1;
+                    summary.last_block_height = block_height;
                     self.state
                         .auction_summaries
                         .insert(&auction_id, summary)
                         .unwrap();
                 }
 
+                let bid = BidRecord {
+                    bid_id,
+                    auction_id,
+                    user_chain,
+                    bidder,
+                    quantity,
+                    amount_paid,
+                    timestamp: self.runtime.system_time(),
+                    claimed: false,  // Not yet claimed
+                    delivery,
+                    payment_asset,
+                    recipient,
+                };
+
+                // Track payer -> recipient relationship for gifted bids
+                if let Some(recipient) = recipient {
+                    let mut sent = self
+                        .state
+                        .gifts_by_payer
+                        .get(&bidder)
+                        .await
+                        .unwrap()
+                        .unwrap_or_default();
+                    sent.push(bid.clone());
+                    self.state.gifts_by_payer.insert(&bidder, sent).unwrap();
+
+                    let mut received = self
+                        .state
+                        .gifts_by_recipient
+                        .get(&recipient.owner)
+                        .await
+                        .unwrap()
+                        .unwrap_or_default();
+                    received.push(bid.clone());
+                    self.state
+                        .gifts_by_recipient
+                        .insert(&recipient.owner, received)
+                        .unwrap();
+                }
+
                 // Store bid in history
                 if let Some(mut history) = self.state.bid_history.get(&auction_id).await.unwrap() {
-                    history.push(BidRecord {
-                        bid_id,
-                        auction_id,
-                        user_chain,
-                        quantity,
-                        amount_paid,
-                        timestamp: self.runtime.system_time(),
-                        claimed: false,  // Not yet claimed
-                    });
+                    history.push(bid);
                     self.state.bid_history.insert(&auction_id, history).unwrap();
                 }
             }
@@ -221,6 +400,8 @@ impl IndexerContract {
                 total_bids: _,
                 reason: _,
             } => {
+                self.migrate_auction(auction_id).await;
+
                 if let Some(mut summary) = self
                     .state
                     .auction_summaries
@@ -242,7 +423,11 @@ impl IndexerContract {
                 clearing_price: _,
                 total_bidders,
                 total_sold: _,
+                bid_digest,
+                digest_start,
             } => {
+                self.migrate_auction(auction_id).await;
+
                 if let Some(mut summary) = self
                     .state
                     .auction_summaries
@@ -252,11 +437,18 @@ impl IndexerContract {
                 {
                     summary.status = AuctionStatus::Settled;
                     summary.total_bidders = total_bidders;
+                    summary.bid_digest = bid_digest;
+                    summary.digest_start = digest_start;
                     self.state
                         .auction_summaries
                         .insert(&auction_id, summary)
                         .unwrap();
                 }
+
+                // Unregistered indexers would only be rejected; ConfirmArchive resends later
+                if *self.state.registered.get() {
+                    self.confirm_archive(auction_id).await;
+                }
             }
 
             AuctionEvent::SettlementClaimed {
@@ -265,16 +457,30 @@ impl IndexerContract {
                 allocated_quantity: _,
                 clearing_price: _,
                 total_cost: _,
-                refund: _,
+                payments: _,
             } => {
                 // Log only, no state changes needed
                 // Settlement claims are tracked on AAC chain, not in indexer
             }
 
+            AuctionEvent::UnclaimedSwept { .. } => {
+                // Sweep event - informational only, claims are tracked on AAC chain
+            }
+
+            AuctionEvent::SettlementsDistributed { .. } => {
+                // Distribution progress event - informational only, no state update needed
+            }
+
+            AuctionEvent::AllSettlementsClaimed { .. } => {
+                // Batch claim event - informational only, claims are tracked on AAC chain
+            }
+
             AuctionEvent::AuctionCancelled {
                 auction_id,
                 reason: _,
             } => {
+                self.migrate_auction(auction_id).await;
+
                 if let Some(mut summary) = self
                     .state
                     .auction_summaries
@@ -297,6 +503,60 @@ impl IndexerContract {
             AuctionEvent::RefundIssued { .. } => {
                 // Refund issued event - informational only, no state update needed
             }
+
+            AuctionEvent::ProceedsPaid { .. } => {
+                // Proceeds paid event - informational only, no state update needed
+            }
+
+            AuctionEvent::IndexerRegistered {
+                indexer_chain,
+                indexer_app,
+            } => {
+                if indexer_chain == self.runtime.chain_id()
+                    && indexer_app == self.runtime.application_id().forget_abi()
+                {
+                    self.state.registered.set(true);
+                }
+            }
+
+            AuctionEvent::IndexerUnregistered { indexer_chain } => {
+                if indexer_chain == self.runtime.chain_id() {
+                    self.state.registered.set(false);
+                }
+            }
+
+            AuctionEvent::ArchiveConfirmed { .. } | AuctionEvent::ArchiveRejected { .. } => {
+                // Archive handshake outcome - informational only, pruning is gated on AAC chain
+            }
+
+            AuctionEvent::BasketAccepted {
+                basket_id,
+                user_chain,
+                bidder,
+                legs,
+                timestamp,
+            } => {
+                let basket = BasketRecord {
+                    basket_id,
+                    user_chain,
+                    bidder,
+                    legs,
+                    timestamp,
+                };
+                self.state.baskets.insert(&basket_id, basket).unwrap();
+            }
+
+            AuctionEvent::BasketRejected { .. } => {
+                // Basket rejected event - informational only, nothing was charged
+            }
+
+            AuctionEvent::PoolOpened { .. }
+            | AuctionEvent::PoolContributed { .. }
+            | AuctionEvent::PoolBidPlaced { .. }
+            | AuctionEvent::PoolCancelled { .. }
+            | AuctionEvent::PoolShareClaimed { .. } => {
+                // Pool events - informational only, the pool's bid arrives as BidAccepted
+            }
         }
     }
 }
@@ -27,6 +27,19 @@ pub enum IndexerOperation {
         aac_chain: ChainId,
         auction_app: ApplicationId,
     },
+
+    /// Upgrade up to max_entries stored records towards the current schema version
+    /// (admin only; without an admin, a chain owner becomes it). Repeat until
+    /// migrationStatus reports complete.
+    Migrate {
+        max_entries: u64,
+    },
+
+    /// Resend the archive confirmation for a settled auction to the AAC, e.g. after this
+    /// indexer was registered late or the AAC emitted ArchiveRejected
+    ConfirmArchive {
+        auction_id: u64,
+    },
 }
 
 #[derive(Debug, Default, Deserialize, Serialize)]
@@ -38,6 +51,11 @@ pub enum IndexerResponse {
         aac_chain: ChainId,
         auction_app: ApplicationId,
     },
+
+    Migrated {
+        entries: u64,
+        schema_version: u32,
+    },
 }
 
 /// Indexer Parameters - Empty, configuration is done via Initialize operation
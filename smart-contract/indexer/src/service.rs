@@ -4,13 +4,15 @@ mod state;
 
 use async_graphql::{EmptySubscription, Object, Request, Response, Schema};
 use linera_sdk::graphql::GraphQLMutationRoot;
-use linera_sdk::linera_base_types::{AccountOwner, Amount, WithServiceAbi};
+use linera_sdk::linera_base_types::{AccountOwner, Amount, BlockHeight, WithServiceAbi};
 use linera_sdk::views::View;
 use linera_sdk::{Service, ServiceRuntime};
 use std::sync::Arc;
-use self::state::{IndexerState, SubscriptionInfoView};
+use self::state::{IndexerState, SubscriptionInfoView, SCHEMA_VERSION};
 use indexer::IndexerAbi;
-use shared::types::{AuctionId, AuctionStatus, AuctionSummary, BidRecord};
+use shared::types::{
+    AuctionId, AuctionStatus, AuctionSummary, BasketRecord, BidRecord, MigrationStatus, PriceClock,
+};
 
 pub struct IndexerService {
     state: Arc<IndexerState>,
@@ -250,8 +252,14 @@ impl QueryRoot {
     }
 
     /// Calculate current price for an auction
-    /// Price is calculated on-demand based on auction parameters and current time
-    async fn current_price(&self, auction_id: AuctionId) -> Result<Amount, String> {
+    /// Price is calculated on-demand based on auction parameters and current time.
+    /// Block-height clocked auctions are priced at `aac_block_height` (the caller's view of the
+    /// AAC tip); without it they use the latest height seen in events, i.e. the price at the last event.
+    async fn current_price(
+        &self,
+        auction_id: AuctionId,
+        aac_block_height: Option<BlockHeight>,
+    ) -> Result<Amount, String> {
         let summary = self
             .state
             .auction_summaries
@@ -260,18 +268,31 @@ impl QueryRoot {
             .map_err(|e| e.to_string())?
             .ok_or_else(|| "Auction not found".to_string())?;
 
-        // Get current time - Note: This is the indexer's local time
-        let current_time = linera_sdk::linera_base_types::Timestamp::now();
-
-        // Use shared utility function
-        let price = shared::calculate_current_price(
-            summary.start_price,
-            summary.floor_price,
-            summary.price_decay_amount,
-            summary.price_decay_interval,
-            summary.start_time,
-            current_time,
-        );
+        // Use shared utility functions
+        let price = match summary.price_clock {
+            PriceClock::WallClock => {
+                // Get current time - Note: This is the indexer's local time
+                let current_time = linera_sdk::linera_base_types::Timestamp::now();
+
+                shared::calculate_current_price(
+                    summary.start_price,
+                    summary.floor_price,
+                    summary.price_decay_amount,
+                    summary.price_decay_interval,
+                    summary.start_time,
+                    current_time,
+                )
+            }
+            PriceClock::BlockHeight { start_height } => shared::calculate_price_at_height(
+                summary.start_price,
+                summary.floor_price,
+                summary.price_decay_amount,
+                summary.price_decay_interval,
+                start_height,
+                aac_block_height
+                    .map_or(summary.last_block_height, |height| height.max(summary.last_block_height)),
+            ),
+        };
 
         Ok(price)
     }
@@ -322,4 +343,47 @@ impl QueryRoot {
 
         Ok(result)
     }
+
+    /// Get bids an owner paid for on behalf of gift recipients
+    async fn gifts_sent(&self, payer: AccountOwner) -> Result<Vec<BidRecord>, String> {
+        Ok(self
+            .state
+            .gifts_by_payer
+            .get(&payer)
+            .await
+            .map_err(|e| e.to_string())?
+            .unwrap_or_default())
+    }
+
+    /// Get bids other owners paid for with this owner as the recipient
+    async fn gifts_received(&self, recipient: AccountOwner) -> Result<Vec<BidRecord>, String> {
+        Ok(self
+            .state
+            .gifts_by_recipient
+            .get(&recipient)
+            .await
+            .map_err(|e| e.to_string())?
+            .unwrap_or_default())
+    }
+
+    /// Get an accepted basket purchase with its legs
+    async fn basket(&self, basket_id: u64) -> Result<Option<BasketRecord>, String> {
+        self.state.baskets.get(&basket_id).await.map_err(|e| e.to_string())
+    }
+
+    /// Check whether the AAC has registered this indexer to confirm archives
+    async fn is_registered(&self) -> bool {
+        *self.state.registered.get()
+    }
+
+    /// Get the stored schema version and progress of a pending Migrate
+    async fn migration_status(&self) -> MigrationStatus {
+        let schema_version = *self.state.schema_version.get();
+        MigrationStatus {
+            schema_version,
+            target_version: SCHEMA_VERSION,
+            migrated: *self.state.migration_cursor.get(),
+            complete: schema_version >= SCHEMA_VERSION,
+        }
+    }
 }
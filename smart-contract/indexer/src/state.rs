@@ -1,7 +1,16 @@
 use async_graphql::SimpleObject;
-use linera_sdk::linera_base_types::{AccountOwner, ApplicationId, ChainId};
+use linera_sdk::linera_base_types::{
+    AccountOwner, Amount, ApplicationId, BlockHeight, ChainId, Timestamp,
+};
 use linera_sdk::views::{linera_views, MapView, RegisterView, RootView, ViewStorageContext};
-use shared::types::{AuctionId, AuctionSummary, BidRecord};
+use shared::types::{
+    AuctionId, AuctionStatus, AuctionSummary, BasketRecord, BidRecord, LegacyBidRecord,
+    PaymentAsset, PriceClock,
+};
+
+/// Schema version written by this code; `Migrate` upgrades older state to it
+/// - 1: summaries and bid history in the current layout (payment assets, bidders, digests)
+pub const SCHEMA_VERSION: u32 = 1;
 
 /// Subscription information (stored in state)
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -18,15 +27,71 @@ pub struct SubscriptionInfoView {
     pub initialized: bool,
 }
 
+/// Auction summary as stored before schema version 1 (frozen: existing rows decode with it)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LegacyAuctionSummary {
+    pub auction_id: AuctionId,
+    pub item_name: String,
+    pub total_supply: u64,
+    pub start_price: Amount,
+    pub floor_price: Amount,
+    pub price_decay_interval: u64,
+    pub price_decay_amount: Amount,
+    pub start_time: Timestamp,
+    pub end_time: Timestamp,
+    pub creator: AccountOwner,
+    pub payment_token_app: ApplicationId,
+    pub current_price: Amount,
+    pub sold: u64,
+    pub clearing_price: Option<Amount>,
+    pub status: AuctionStatus,
+    pub total_bids: u64,
+    pub total_bidders: u64,
+}
+
+impl LegacyAuctionSummary {
+    /// Convert to the current layout: a wall-clock auction paid in its single token
+    pub fn into_summary(self) -> AuctionSummary {
+        AuctionSummary {
+            auction_id: self.auction_id,
+            item_name: self.item_name,
+            total_supply: self.total_supply,
+            start_price: self.start_price,
+            floor_price: self.floor_price,
+            price_decay_interval: self.price_decay_interval,
+            price_decay_amount: self.price_decay_amount,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            creator: self.creator,
+            payment_asset: PaymentAsset::Fungible(self.payment_token_app),
+            accepted_assets: Vec::new(),
+            price_clock: PriceClock::WallClock,
+            current_price: self.current_price,
+            sold: self.sold,
+            clearing_price: self.clearing_price,
+            status: self.status,
+            total_bids: self.total_bids,
+            total_bidders: self.total_bidders,
+            last_block_height: BlockHeight::ZERO,
+            bid_digest: None,
+            digest_start: 0,
+        }
+    }
+}
+
 /// Indexer state - stores materialized views of auction data
+/// Views are stored under their field position, so the first five fields keep the
+/// positions (and record layouts) of the original release; new fields are appended.
 #[derive(RootView)]
 #[view(context = ViewStorageContext)]
 pub struct IndexerState {
-    /// Materialized auction summaries
-    pub auction_summaries: MapView<AuctionId, AuctionSummary>,
+    /// Auction summaries in the original layout
+    /// No longer written; Migrate moves them into auction_summaries (schema version 1).
+    pub legacy_summaries: MapView<AuctionId, LegacyAuctionSummary>,
 
-    /// Full bid history (never pruned, unlike AAC)
-    pub bid_history: MapView<AuctionId, Vec<BidRecord>>,
+    /// Bid history in the original layout
+    /// No longer written; Migrate moves it into bid_history with its summary.
+    pub legacy_bid_history: MapView<AuctionId, Vec<LegacyBidRecord>>,
 
     /// Index: creator -> auction IDs
     /// Enables efficient "auctions by creator" queries
@@ -37,4 +102,33 @@ pub struct IndexerState {
 
     /// Current subscription information (set during Initialize)
     pub subscription: RegisterView<Option<SubscriptionInfo>>,
+
+    /// Materialized auction summaries
+    pub auction_summaries: MapView<AuctionId, AuctionSummary>,
+
+    /// Full bid history (never pruned, unlike AAC)
+    pub bid_history: MapView<AuctionId, Vec<BidRecord>>,
+
+    /// Index: gift payer -> bids they paid for on behalf of someone else
+    pub gifts_by_payer: MapView<AccountOwner, Vec<BidRecord>>,
+
+    /// Index: gift recipient -> bids allocated to them by someone else
+    pub gifts_by_recipient: MapView<AccountOwner, Vec<BidRecord>>,
+
+    /// Accepted basket purchases by basket ID (legs link to bid_history by bid_id)
+    pub baskets: MapView<u64, BasketRecord>,
+
+    /// Indexer admin: the signer that instantiated the application, or on upgraded
+    /// indexers the chain owner that first ran Migrate (may run Migrate)
+    pub admin: RegisterView<Option<AccountOwner>>,
+
+    /// Schema version of the stored data (0 = written before versioning)
+    pub schema_version: RegisterView<u32>,
+
+    /// Entries migrated so far in the pending migration step
+    pub migration_cursor: RegisterView<u64>,
+
+    /// Whether the AAC registered this indexer to confirm archives
+    /// (tracked from IndexerRegistered / IndexerUnregistered events)
+    pub registered: RegisterView<bool>,
 }
@@ -1,7 +1,12 @@
-use linera_sdk::linera_base_types::{AccountOwner, Amount, ChainId, Timestamp};
+use linera_sdk::linera_base_types::{
+    Account, AccountOwner, Amount, ApplicationId, BlockHeight, ChainId, CryptoHash, Timestamp,
+};
 use serde::{Deserialize, Serialize};
 
-use crate::types::AuctionId;
+use crate::types::{
+    AcceptedAsset, AssetSettlement, AuctionId, BasketLegFill, PaymentAsset, PriceClock,
+    SettlementDelivery,
+};
 
 /// Stream name for all auction events
 pub const AUCTION_STREAM: &[u8] = b"fairdrop_auctions";
@@ -26,7 +31,10 @@ pub enum AuctionEvent {
         start_time: Timestamp,
         end_time: Timestamp,
         creator: AccountOwner, // Creator's account (for fund transfers)
-        payment_token_app: linera_sdk::linera_base_types::ApplicationId, // Payment token app
+        payment_asset: PaymentAsset, // Unit of account
+        accepted_assets: Vec<AcceptedAsset>, // Other assets accepted, with conversion rates
+        price_clock: PriceClock, // Clock driving price decay
+        block_height: BlockHeight, // AAC block height at creation
     },
 
     /// Bid accepted
@@ -34,10 +42,16 @@ pub enum AuctionEvent {
         auction_id: AuctionId,
         bid_id: u64,
         user_chain: ChainId,
-        quantity: u64,
-        amount_paid: Amount, // Total amount paid by user
+        bidder: AccountOwner, // Owner who paid for the bid
+        requested_quantity: u64, // Quantity the user asked for
+        quantity: u64, // Quantity accepted (may be less under FillPolicy::Partial)
+        amount_paid: Amount, // Total amount paid by user, in payment_asset
         total_sold: u64,
         remaining: u64,
+        block_height: BlockHeight, // AAC block height at acceptance
+        delivery: SettlementDelivery, // Where refunds for this bid are sent
+        payment_asset: PaymentAsset, // Asset amount_paid is denominated in
+        recipient: Option<Account>, // Gift recipient of the allocation, if any
     },
 
     /// Bid rejected
@@ -61,6 +75,8 @@ pub enum AuctionEvent {
         clearing_price: Amount,
         total_bidders: u64,
         total_sold: u64,
+        bid_digest: Option<CryptoHash>, // Hash chain over the accepted bids after digest_start
+        digest_start: u64, // Bids migrated from the legacy layout, which the digest skips
     },
 
     /// User claimed settlement
@@ -69,8 +85,31 @@ pub enum AuctionEvent {
         user_chain: ChainId,
         allocated_quantity: u64,
         clearing_price: Amount,
-        total_cost: Amount,
-        refund: Amount,
+        total_cost: Amount, // Unit of account
+        payments: Vec<AssetSettlement>, // Per-asset costs and refunds
+    },
+
+    /// One page of unclaimed settlements swept after the claim deadline
+    UnclaimedSwept {
+        auction_id: AuctionId,
+        bidders: u64, // Owners whose bids were settled by this page
+        forfeited: Vec<(PaymentAsset, Amount)>, // Refunds sent to the treasury (empty when pushed to bidders)
+        complete: bool, // Last page: every position is now claimed
+    },
+
+    /// Auto-distribution progress: `distributed` of `total` bidder chains settled
+    SettlementsDistributed {
+        auction_id: AuctionId,
+        distributed: u64,
+        total: u64,
+    },
+
+    /// Batch claim processed (each auction also has its own SettlementClaimed)
+    AllSettlementsClaimed {
+        user_chain: ChainId,
+        bidder: AccountOwner,
+        auction_ids: Vec<AuctionId>,
+        refunds: Vec<(PaymentAsset, Amount)>, // One transfer per asset
     },
 
     /// Auction cancelled by creator
@@ -90,13 +129,100 @@ pub enum AuctionEvent {
     /// Refund issued to user after settlement
     RefundIssued {
         auction_id: AuctionId,
-        user_chain: ChainId,
+        user_chain: ChainId, // Chain the refund was delivered to
         refund_amount: Amount,
     },
+
+    /// Basket accepted: every leg filled (each leg also has its own BidAccepted)
+    BasketAccepted {
+        basket_id: u64,
+        user_chain: ChainId,
+        bidder: AccountOwner,
+        legs: Vec<BasketLegFill>,
+        timestamp: Timestamp,
+    },
+
+    /// Basket rejected: nothing was charged (the failing leg's BidRejected carries the reason)
+    BasketRejected {
+        basket_id: u64,
+        user_chain: ChainId,
+        auction_id: AuctionId, // Leg that failed
+    },
+
+    /// Pooled group bid opened
+    PoolOpened {
+        pool_id: u64,
+        auction_id: AuctionId,
+        organiser: AccountOwner,
+        target_quantity: u64,
+    },
+
+    /// Funds contributed to a pool
+    PoolContributed {
+        pool_id: u64,
+        contributor: AccountOwner,
+        amount: Amount, // In the pool's payment asset
+        total_contributed: Amount,
+    },
+
+    /// Pool's combined bid accepted
+    PoolBidPlaced {
+        pool_id: u64,
+        auction_id: AuctionId,
+        bid_id: u64,
+        quantity: u64,
+    },
+
+    /// Pool cancelled by the organiser
+    PoolCancelled {
+        pool_id: u64,
+    },
+
+    /// Contributor claimed their pro rata share of a pool
+    PoolShareClaimed {
+        pool_id: u64,
+        contributor: AccountOwner,
+        allocated_quantity: u64,
+        refund: Amount,
+    },
+
+    /// Sale proceeds (sold × clearing price) paid to the creator by ClaimProceeds
+    ProceedsPaid {
+        auction_id: AuctionId,
+        creator: AccountOwner,
+        payment_asset: PaymentAsset,
+        amount: Amount,
+    },
+
+    /// The admin accepted archive confirmations from indexer_app on indexer_chain
+    IndexerRegistered {
+        indexer_chain: ChainId,
+        indexer_app: ApplicationId,
+    },
+
+    /// The admin stopped accepting archive confirmations from indexer_chain
+    IndexerUnregistered {
+        indexer_chain: ChainId,
+    },
+
+    /// A registered indexer confirmed it holds the auction's full bid history (pruning allowed)
+    ArchiveConfirmed {
+        auction_id: AuctionId,
+        indexer_chain: ChainId,
+    },
+
+    /// Archive confirmation refused (pruning stays blocked)
+    ArchiveRejected {
+        auction_id: AuctionId,
+        indexer_chain: ChainId,
+        reason: String,
+    },
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
 pub enum ClearReason {
     SupplyExhausted,
     TimeExpired,
+    FloorDurationElapsed, // EndCondition::FloorReachedFor
+    RaiseTargetReached, // EndCondition::RaiseTarget
 }
@@ -7,10 +7,17 @@ pub mod utils;
 pub use events::{AuctionEvent, ClearReason, AUCTION_STREAM};
 pub use messages::{AuctionMessage, IndexerMessage};
 pub use types::{
-    AuctionId, AuctionParams, AuctionStatus, AuctionSummary, BidRecord, SettlementResult,
-    UserCommitment,
+    AcceptedAsset, AssetPaid, AssetSettlement, AuctionId, AuctionParams, AuctionStatus,
+    AuctionSummary, BasketLeg, BasketLegFill, BasketRecord, BidRecord, EndCondition, FillPolicy,
+    LegacyBidRecord, MigrationStatus, PaymentAsset, PaymentMethod, PaymentSource, PaymentTokenInfo,
+    PoolStatus, PriceClock, SettlementDelivery, SettlementResult, UnclaimedPolicy, UserCommitment,
+    DEFAULT_CLAIM_WINDOW,
+};
+pub use utils::{
+    asset_settlements, bid_log_digest, calculate_current_price, calculate_price_at_height,
+    chain_bid_digest, convert_to_asset, convert_to_asset_floor, floor_reached_after,
+    merge_settlement, pro_rata_amount, pro_rata_units,
 };
-pub use utils::calculate_current_price;
 
 // Also export the ABI type for external reference
 pub struct AuctionAbi;
@@ -1,7 +1,12 @@
-use linera_sdk::linera_base_types::{ChainId};
+use linera_sdk::linera_base_types::{
+    Account, AccountOwner, Amount, ApplicationId, ChainId, CryptoHash, Timestamp,
+};
 use serde::{Deserialize, Serialize};
 
-use crate::types::{AuctionId, AuctionParams, SettlementResult};
+use crate::types::{
+    AuctionId, AuctionParams, BasketLeg, FillPolicy, PaymentAsset, PaymentSource,
+    SettlementDelivery, SettlementResult,
+};
 
 /// Messages for the Auction Application (used by both AAC and UIC chains)
 /// Since AAC and UIC are the same application,
@@ -22,6 +27,46 @@ pub enum AuctionMessage {
         auction_id: AuctionId,
         user_chain: ChainId,
         quantity: u64, // How many units to bid for
+        max_price_per_unit: Option<Amount>, // Slippage limit (None = accept any price)
+        deadline: Option<Timestamp>, // Latest AAC time the bid may be processed at
+        fill_policy: FillPolicy, // How to handle bids larger than the remaining supply
+        submitted_at: Timestamp, // UIC block timestamp (used for pricing in fairness mode)
+        delivery: SettlementDelivery, // Where refunds are delivered
+        payment: PaymentSource, // How the bid is funded
+        payment_asset: Option<PaymentAsset>, // Asset the bid pays in (None = the auction's payment_asset)
+        recipient: Option<Account>, // Account the allocation is delivered to (None = the bidder)
+    },
+
+    /// User buys every leg or none (from UIC chain)
+    BuyBasket {
+        user_chain: ChainId,
+        legs: Vec<BasketLeg>,
+        delivery: SettlementDelivery, // Where refunds for each leg are sent
+        payment: PaymentSource, // AacBalance or Deposit
+    },
+
+    /// Credit the sender's deposit ledger (from UIC chain)
+    /// Sent in the same bundle as the fungible Credit into the AAC escrow
+    Deposit {
+        payment_asset: PaymentAsset,
+        amount: Amount,
+    },
+
+    /// Withdraw from the sender's deposit ledger to their account on user_chain (from UIC chain)
+    Withdraw {
+        payment_asset: PaymentAsset,
+        amount: Amount,
+        user_chain: ChainId,
+    },
+
+    /// Authorise a bidder to place BuyFor bids for the sender (from UIC chain)
+    AuthorizeBidder {
+        bidder: AccountOwner,
+    },
+
+    /// Revoke a bidder's BuyFor authorisation (from UIC chain)
+    RevokeBidder {
+        bidder: AccountOwner,
     },
 
     /// User claims settlement (from UIC chain)
@@ -30,6 +75,51 @@ pub enum AuctionMessage {
         user_chain: ChainId,
     },
 
+    /// User claims settlement for several auctions at once (from UIC chain)
+    ClaimAll {
+        auction_ids: Vec<AuctionId>,
+        user_chain: ChainId,
+    },
+
+    /// Open a pooled group bid (from UIC chain, sender is the organiser)
+    OpenPool {
+        auction_id: AuctionId,
+        target_quantity: u64,
+        max_price_per_unit: Amount,
+        payment_asset: Option<PaymentAsset>,
+    },
+
+    /// Contribute to a pool (from UIC chain)
+    /// Sent in the same bundle as the Credit into the AAC escrow
+    ContributeToPool {
+        pool_id: u64,
+        payment_asset: PaymentAsset,
+        amount: Amount,
+    },
+
+    /// Place the pool's combined bid (from UIC chain, organiser only)
+    PlacePoolBid {
+        pool_id: u64,
+    },
+
+    /// Cancel an open pool (from UIC chain, organiser only)
+    CancelPool {
+        pool_id: u64,
+    },
+
+    /// Claim a contributor's share of a pool (from UIC chain)
+    ClaimPoolShare {
+        pool_id: u64,
+    },
+
+    /// The indexer holds the settled auction's full bid history (from a registered indexer chain)
+    ArchiveConfirmed {
+        auction_id: AuctionId,
+        bid_count: u64,
+        digest: Option<CryptoHash>, // bid_log_digest over the indexer's bid_history
+        indexer_app: ApplicationId, // Application that called ConfirmArchive
+    },
+
     // ─────────────────────────────────────────────────────────
     // Messages received by UIC Chains
     // ─────────────────────────────────────────────────────────
@@ -39,6 +129,18 @@ pub enum AuctionMessage {
         auction_id: AuctionId,
         result: SettlementResult,
     },
+
+    /// Settlement results for every auction claimed by a ClaimAll (from AAC)
+    SettlementResults {
+        results: Vec<(AuctionId, SettlementResult)>,
+    },
+
+    /// A contributor's pro rata share of a pool (from AAC after ClaimPoolShare)
+    PoolShareSettled {
+        pool_id: u64,
+        auction_id: AuctionId,
+        result: SettlementResult,
+    },
 }
 
 /// Messages sent to Indexer (not used - Indexer uses events only)
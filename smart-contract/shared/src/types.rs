@@ -1,9 +1,14 @@
 use async_graphql::{scalar, InputObject, SimpleObject};
-use linera_sdk::linera_base_types::{AccountOwner, Amount, ApplicationId, ChainId, Timestamp};
+use linera_sdk::linera_base_types::{
+    Account, AccountOwner, Amount, ApplicationId, BlockHeight, ChainId, CryptoHash, Timestamp,
+};
 use serde::{Deserialize, Serialize};
 
 pub type AuctionId = u64;
 
+/// Default time bidders have to claim after settlement: 90 days, in microseconds
+pub const DEFAULT_CLAIM_WINDOW: u64 = 90 * 24 * 60 * 60 * 1_000_000;
+
 /// Auction configuration parameters (for GraphQL input)
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, InputObject)]
 #[graphql(name = "AuctionParamsInput")]
@@ -12,12 +17,18 @@ pub struct AuctionParamsInput {
     pub total_supply: u64, // Total quantity for sale
     pub start_price: Amount, // Starting price per unit
     pub floor_price: Amount, // Minimum price (reserve)
-    pub price_decay_interval: u64, // Microseconds between price drops
+    pub price_decay_interval: u64, // Microseconds (or blocks, see price_clock) between price drops
     pub price_decay_amount: Amount, // Amount to decrease per interval
     pub start_time: Timestamp,
     pub end_time: Timestamp,
     pub creator: AccountOwner, // Creator's account (for fund transfers)
-    pub payment_token_app: ApplicationId, // Fungible token application for payments
+    pub payment_asset: PaymentAsset, // Native chain token or fungible application used for payments (the unit of account)
+    pub accepted_assets: Option<Vec<AcceptedAsset>>, // Other assets bids may pay in (default: none)
+    pub fair_ordering_window: Option<u64>, // Fairness mode: max microseconds between UIC submission and AAC processing
+    pub price_clock: Option<PriceClock>, // Clock driving price decay (default: WallClock)
+    pub end_conditions: Option<Vec<EndCondition>>, // Extra ways to end early (default: none)
+    pub auto_distribute: Option<bool>, // Push settlements to bidders after settling (default: false, claim-only)
+    pub claim_window: Option<u64>, // Microseconds after settlement before SweepUnclaimed (default: 90 days)
 }
 
 /// Auction configuration parameters (for output and internal use)
@@ -32,7 +43,13 @@ pub struct AuctionParams {
     pub start_time: Timestamp,
     pub end_time: Timestamp,
     pub creator: AccountOwner,
-    pub payment_token_app: ApplicationId,
+    pub payment_asset: PaymentAsset,
+    pub accepted_assets: Vec<AcceptedAsset>,
+    pub fair_ordering_window: Option<u64>,
+    pub price_clock: PriceClock,
+    pub end_conditions: Vec<EndCondition>,
+    pub auto_distribute: bool,
+    pub claim_window: u64,
 }
 
 // Conversion from input to internal type
@@ -48,11 +65,74 @@ impl From<AuctionParamsInput> for AuctionParams {
             start_time: input.start_time,
             end_time: input.end_time,
             creator: input.creator,
-            payment_token_app: input.payment_token_app,
+            payment_asset: input.payment_asset,
+            accepted_assets: input.accepted_assets.unwrap_or_default(),
+            fair_ordering_window: input.fair_ordering_window,
+            price_clock: input.price_clock.unwrap_or_default(),
+            end_conditions: input.end_conditions.unwrap_or_default(),
+            auto_distribute: input.auto_distribute.unwrap_or_default(),
+            claim_window: input.claim_window.unwrap_or(DEFAULT_CLAIM_WINDOW),
+        }
+    }
+}
+
+impl AuctionParams {
+    /// Conversion rate for paying in `asset`: asset units per unit of account
+    /// `payment_asset` is the unit of account (rate 1). Returns None if the asset is not accepted.
+    pub fn rate_for(&self, asset: PaymentAsset) -> Option<Amount> {
+        if asset == self.payment_asset {
+            return Some(Amount::ONE);
         }
+        self.accepted_assets
+            .iter()
+            .find(|accepted| accepted.asset == asset)
+            .map(|accepted| accepted.rate)
     }
 }
 
+/// An additional payment asset accepted at a fixed, creator-set rate
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, SimpleObject, InputObject)]
+#[graphql(input_name = "AcceptedAssetInput")]
+pub struct AcceptedAsset {
+    pub asset: PaymentAsset,
+    pub rate: Amount, // Asset units per unit of account (Amount::ONE = 1:1)
+}
+
+scalar!(PaymentAsset);
+/// Asset an auction is paid in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum PaymentAsset {
+    Native, // The chain's native token, moved with system transfers
+    Fungible(ApplicationId), // A `fungible` token application
+}
+
+/// Approved fungible payment token (registry entry, stored on AAC)
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, SimpleObject)]
+pub struct PaymentTokenInfo {
+    pub token_app: ApplicationId,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+scalar!(PriceClock);
+/// Clock source used to measure price decay
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub enum PriceClock {
+    #[default]
+    WallClock, // price_decay_interval is in microseconds since start_time
+    BlockHeight {
+        start_height: BlockHeight, // price_decay_interval is in AAC blocks since this height
+    },
+}
+
+scalar!(EndCondition);
+/// Additional conditions that end an auction (supply exhaustion and end_time always apply)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+pub enum EndCondition {
+    FloorReachedFor(u64), // End this many clock ticks (microseconds or blocks) after price first reaches the floor
+    RaiseTarget(Amount), // End once sold × current price reaches this amount
+}
+
 scalar!(AuctionStatus);
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
 pub enum AuctionStatus {
@@ -63,9 +143,92 @@ pub enum AuctionStatus {
     Cancelled, // Cancelled by creator (only Scheduled auctions can be cancelled)
 }
 
+scalar!(PoolStatus);
+/// Lifecycle of a pooled group bid
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+pub enum PoolStatus {
+    Open, // Accepting contributions
+    BidPlaced, // Combined bid accepted by the auction
+    Cancelled, // Cancelled by the organiser - contributions are refundable
+}
+
+scalar!(UnclaimedPolicy);
+/// What SweepUnclaimed does with refunds nobody claimed before the claim deadline
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub enum UnclaimedPolicy {
+    #[default]
+    PushToBidders, // Send each refund to where the bidder asked for it
+    ForfeitToTreasury(Account), // Send all unclaimed refunds to the treasury account
+}
+
+scalar!(FillPolicy);
+/// How the AAC may fill a bid when less supply remains than requested
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub enum FillPolicy {
+    #[default]
+    Partial, // Accept whatever remains (requested.min(remaining))
+    AllOrNothing, // Reject unless the full quantity can be filled
+    MinimumFill(u64), // Reject unless at least this many units can be filled
+}
+
+scalar!(SettlementDelivery);
+/// Where refunds from a bid are delivered
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub enum SettlementDelivery {
+    #[default]
+    Aac, // Credit the bidder's account on the AAC chain
+    UserChain, // Credit the bidder's account on the bidding user chain (cross-chain Credit)
+    Deposit, // Credit the bidder's deposit ledger on the AAC (no token transfer)
+}
+
+scalar!(PaymentMethod);
+/// How a user pays for a bid (chosen on the UIC)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub enum PaymentMethod {
+    #[default]
+    AacBalance, // Pay from the bidder's fungible balance on the AAC chain
+    UserChain(PaymentAsset), // Escrow max_price × quantity from the bidder's balance on the user chain
+    Deposit, // Pay from the bidder's deposit ledger on the AAC
+}
+
+/// How a PlaceBid is funded (resolved by the UIC before sending)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+pub enum PaymentSource {
+    AacBalance, // AAC collects payment from the bidder's AAC balance
+    Deposit, // AAC debits the bidder's deposit ledger
+    Pool {
+        pool_id: u64, // Contributions already held in escrow for the pool (AAC internal)
+    },
+    Allowance {
+        owner: AccountOwner, // Beneficiary; AAC uses TransferFrom against their allowance to the auction app
+    },
+    Escrowed {
+        payment_asset: PaymentAsset,
+        amount: Amount, // Already credited to the AAC escrow in the same message bundle
+    },
+}
+
 /// Individual bid record (stored on AAC)
 #[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
 pub struct BidRecord {
+    pub bid_id: u64,
+    pub auction_id: AuctionId,
+    pub user_chain: ChainId,
+    pub bidder: AccountOwner, // Owner who paid; the only one who may claim the bid
+    pub quantity: u64,
+    pub amount_paid: Amount,
+    pub timestamp: Timestamp,
+    pub claimed: bool, // As of acceptance; on the AAC, claims are tracked on the bidder's position
+    pub delivery: SettlementDelivery, // Where refunds for this bid are sent
+    pub payment_asset: PaymentAsset, // Asset amount_paid is denominated in
+    pub recipient: Option<Account>, // Receives the allocation if set (bidder still gets refunds)
+}
+
+/// Bid record as stored before the auction's schema version 2 (frozen: existing rows
+/// on the AAC and in indexers decode with it)
+/// Bids were not attributed to an owner: anyone on user_chain could claim them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegacyBidRecord {
     pub bid_id: u64,
     pub auction_id: AuctionId,
     pub user_chain: ChainId,
@@ -75,11 +238,69 @@ pub struct BidRecord {
     pub claimed: bool,
 }
 
+impl LegacyBidRecord {
+    /// Convert to the current layout: the chain itself owns the bid, refunds go to the
+    /// chain's balance, and the amount was paid in the auction's payment asset
+    pub fn into_bid(self, payment_asset: PaymentAsset) -> BidRecord {
+        BidRecord {
+            bid_id: self.bid_id,
+            auction_id: self.auction_id,
+            user_chain: self.user_chain,
+            bidder: AccountOwner::CHAIN,
+            quantity: self.quantity,
+            amount_paid: self.amount_paid,
+            timestamp: self.timestamp,
+            claimed: self.claimed,
+            delivery: SettlementDelivery::UserChain,
+            payment_asset,
+            recipient: None,
+        }
+    }
+}
+
+/// Units bought and escrow held in one payment asset (part of a bidder's aggregate position)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, SimpleObject)]
+pub struct AssetPaid {
+    pub payment_asset: PaymentAsset,
+    pub quantity: u64,
+    pub amount_paid: Amount, // In payment_asset
+}
+
+/// One leg of a basket purchase
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, SimpleObject, InputObject)]
+#[graphql(input_name = "BasketLegInput")]
+pub struct BasketLeg {
+    pub auction_id: AuctionId,
+    pub quantity: u64, // Filled in full or not at all
+    pub max_price_per_unit: Amount,
+}
+
+/// A filled basket leg
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, SimpleObject)]
+pub struct BasketLegFill {
+    pub auction_id: AuctionId,
+    pub bid_id: u64,
+    pub quantity: u64,
+    pub amount_paid: Amount, // In the auction's payment_asset
+}
+
+/// Accepted basket purchase (materialized by Indexer)
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct BasketRecord {
+    pub basket_id: u64,
+    pub user_chain: ChainId,
+    pub bidder: AccountOwner,
+    pub legs: Vec<BasketLegFill>,
+    pub timestamp: Timestamp,
+}
+
 /// User's local commitment (stored on UIC)
 #[derive(Debug, Clone, Default, Serialize, Deserialize, SimpleObject)]
 pub struct UserCommitment {
     pub total_quantity: u64, // Total quantity bid for
     pub settlement: Option<SettlementResult>,
+    pub gifts_received: Vec<SettlementResult>, // Allocations other users paid for (gifted_from is set)
+    pub pool_shares: Vec<SettlementResult>, // Shares of pooled group bids this chain contributed to
 }
 
 /// Settlement result sent from AAC to UIC
@@ -87,8 +308,20 @@ pub struct UserCommitment {
 pub struct SettlementResult {
     pub allocated_quantity: u64, // Quantity received
     pub clearing_price: Amount, // Final uniform price
-    pub total_cost: Amount, // allocated_quantity × clearing_price
-    pub refund: Amount, // Overpayment refunded
+    pub total_cost: Amount, // (allocated_quantity + gifted_quantity) × clearing_price (unit of account)
+    pub payments: Vec<AssetSettlement>, // Per-asset breakdown of what was paid and refunded
+    pub gifted_quantity: u64, // Units paid for but allocated to recipients
+    pub gifted_from: Option<Account>, // Set on the recipient's copy: who paid for the allocation
+}
+
+/// Settlement of the bids paid in one asset
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, SimpleObject)]
+pub struct AssetSettlement {
+    pub payment_asset: PaymentAsset,
+    pub quantity: u64, // Units paid for in this asset
+    pub paid: Amount, // Held in escrow, in this asset
+    pub cost: Amount, // quantity × clearing_price converted to this asset
+    pub refund: Amount, // paid - cost, returned in this asset
 }
 
 /// Auction summary (materialized by Indexer)
@@ -108,7 +341,9 @@ pub struct AuctionSummary {
     pub start_time: Timestamp,
     pub end_time: Timestamp,
     pub creator: AccountOwner,
-    pub payment_token_app: ApplicationId,
+    pub payment_asset: PaymentAsset,
+    pub accepted_assets: Vec<AcceptedAsset>,
+    pub price_clock: PriceClock,
 
     // ──────────────────────────────────────────────────────────
     // Derived State (computed/updated during auction lifecycle)
@@ -119,4 +354,16 @@ pub struct AuctionSummary {
     pub status: AuctionStatus,
     pub total_bids: u64,
     pub total_bidders: u64,
+    pub last_block_height: BlockHeight, // Latest AAC block height seen in events (for BlockHeight clocks)
+    pub bid_digest: Option<CryptoHash>, // Published at settlement; recompute with utils::bid_log_digest
+    pub digest_start: u64, // Leading bids of the history that bid_digest skips
+}
+
+/// Progress of a state schema migration (reported by the auction and indexer services)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, SimpleObject)]
+pub struct MigrationStatus {
+    pub schema_version: u32, // Version of the stored data (0 = written before versioning)
+    pub target_version: u32, // Version the deployed code expects
+    pub migrated: u64,       // Entries migrated so far in the pending step
+    pub complete: bool,
 }
@@ -1,4 +1,15 @@
-use linera_sdk::linera_base_types::{Amount, Timestamp};
+use linera_sdk::linera_base_types::{
+    Account, AccountOwner, Amount, BcsHashable, BlockHeight, ChainId, CryptoHash, Timestamp,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{
+    AssetPaid, AssetSettlement, AuctionId, AuctionParams, BidRecord, PaymentAsset, SettlementDelivery,
+    SettlementResult,
+};
+
+/// Atto-units per whole token (Amount has 18 decimals)
+const ATTOS_PER_TOKEN: u128 = 1_000_000_000_000_000_000;
 
 /// Calculate current price for an auction based on time-based decay
 /// This is a pure function that is being used in contract, service, indexer
@@ -30,8 +41,307 @@ pub fn calculate_current_price(
     let elapsed = current_time.delta_since(start_time);
     let elapsed_micros = elapsed.as_micros();
 
+    price_after_elapsed(
+        start_price,
+        floor_price,
+        price_decay_amount,
+        price_decay_interval,
+        elapsed_micros,
+    )
+}
+
+/// Calculate current price for an auction whose decay is measured in AAC blocks
+/// (`PriceClock::BlockHeight`)
+///
+/// # Arguments
+/// * `start_price` - Initial price at auction start
+/// * `floor_price` - Minimum price (price floor/reserve)
+/// * `price_decay_amount` - Amount to decrease per interval
+/// * `price_decay_interval` - Blocks between price drops
+/// * `start_height` - AAC block height at which decay starts
+/// * `current_height` - Current AAC block height
+///
+/// # Returns
+/// The calculated current price, guaranteed to be >= floor_price
+pub fn calculate_price_at_height(
+    start_price: Amount,
+    floor_price: Amount,
+    price_decay_amount: Amount,
+    price_decay_interval: u64,
+    start_height: BlockHeight,
+    current_height: BlockHeight,
+) -> Amount {
+    // If decay hasn't started, return start price
+    if current_height < start_height {
+        return start_price;
+    }
+
+    let elapsed_blocks = current_height.0 - start_height.0;
+
+    price_after_elapsed(
+        start_price,
+        floor_price,
+        price_decay_amount,
+        price_decay_interval,
+        elapsed_blocks,
+    )
+}
+
+/// Calculate how many clock ticks (microseconds or blocks) after the start
+/// the price first reaches the floor
+///
+/// # Returns
+/// `Some(0)` if the auction starts at the floor, `None` if the price never decays
+pub fn floor_reached_after(
+    start_price: Amount,
+    floor_price: Amount,
+    price_decay_amount: Amount,
+    price_decay_interval: u64,
+) -> Option<u64> {
+    let gap = u128::from(start_price.saturating_sub(floor_price));
+    if gap == 0 {
+        return Some(0);
+    }
+
+    let decay = u128::from(price_decay_amount);
+    if decay == 0 {
+        return None;
+    }
+
+    let intervals = gap.div_ceil(decay);
+    let ticks = intervals.saturating_mul(price_decay_interval as u128);
+    Some(u64::try_from(ticks).unwrap_or(u64::MAX))
+}
+
+/// Convert an amount in the unit of account into `rate` asset units per unit, rounding up
+/// Used for what bidders owe, so escrow always covers costs.
+pub fn convert_to_asset(amount: Amount, rate: Amount) -> Amount {
+    convert_with_rate(amount, rate, true)
+}
+
+/// Convert an amount in the unit of account into `rate` asset units per unit, rounding down
+/// Used for proceeds paid out of escrow.
+pub fn convert_to_asset_floor(amount: Amount, rate: Amount) -> Amount {
+    convert_with_rate(amount, rate, false)
+}
+
+/// Settle positions at `clearing_price`, grouped by the asset they were paid in
+///
+/// # Returns
+/// One entry per asset, in order of first appearance
+pub fn asset_settlements<'a>(
+    params: &AuctionParams,
+    clearing_price: Amount,
+    paid: impl IntoIterator<Item = &'a AssetPaid>,
+) -> Vec<AssetSettlement> {
+    let mut settlements: Vec<AssetSettlement> = Vec::new();
+
+    for paid in paid {
+        match settlements
+            .iter_mut()
+            .find(|settlement| settlement.payment_asset == paid.payment_asset)
+        {
+            Some(settlement) => {
+                settlement.quantity += paid.quantity;
+                settlement.paid = settlement.paid.saturating_add(paid.amount_paid);
+            }
+            None => settlements.push(AssetSettlement {
+                payment_asset: paid.payment_asset,
+                quantity: paid.quantity,
+                paid: paid.amount_paid,
+                cost: Amount::ZERO,
+                refund: Amount::ZERO,
+            }),
+        }
+    }
+
+    for settlement in &mut settlements {
+        let rate = params
+            .rate_for(settlement.payment_asset)
+            .expect("Bid paid in an asset the auction does not accept");
+        let cost_in_unit = clearing_price.saturating_mul(settlement.quantity as u128);
+        settlement.cost = convert_to_asset(cost_in_unit, rate);
+        settlement.refund = settlement.paid.saturating_sub(settlement.cost);
+    }
+
+    settlements
+}
+
+/// Fold `other` into `settlement`: two claims of the same owner on one auction
+/// (e.g. its own bids and BuyFor bids placed for it from another chain)
+pub fn merge_settlement(settlement: &mut SettlementResult, other: SettlementResult) {
+    settlement.allocated_quantity += other.allocated_quantity;
+    settlement.gifted_quantity += other.gifted_quantity;
+    settlement.total_cost = settlement.total_cost.saturating_add(other.total_cost);
+
+    for payment in other.payments {
+        match settlement
+            .payments
+            .iter_mut()
+            .find(|existing| existing.payment_asset == payment.payment_asset)
+        {
+            Some(existing) => {
+                existing.quantity += payment.quantity;
+                existing.paid = existing.paid.saturating_add(payment.paid);
+                existing.cost = existing.cost.saturating_add(payment.cost);
+                existing.refund = existing.refund.saturating_add(payment.refund);
+            }
+            None => settlement.payments.push(payment),
+        }
+    }
+}
+
+/// One link of an auction's bid hash chain: the previous digest and the bid appended after it
+/// Leaves out the fields an indexer cannot reproduce (timestamp, claimed).
+#[derive(Serialize, Deserialize)]
+struct BidChainLink {
+    previous: Option<CryptoHash>,
+    bid_id: u64,
+    auction_id: AuctionId,
+    user_chain: ChainId,
+    bidder: AccountOwner,
+    quantity: u64,
+    amount_paid: Amount,
+    delivery: SettlementDelivery,
+    payment_asset: PaymentAsset,
+    recipient: Option<Account>,
+}
+
+impl BcsHashable<'_> for BidChainLink {}
+
+/// Extend an auction's bid hash chain with the next accepted bid
+/// `previous` is None for the auction's first bid.
+pub fn chain_bid_digest(previous: Option<CryptoHash>, bid: &BidRecord) -> CryptoHash {
+    CryptoHash::new(&BidChainLink {
+        previous,
+        bid_id: bid.bid_id,
+        auction_id: bid.auction_id,
+        user_chain: bid.user_chain,
+        bidder: bid.bidder,
+        quantity: bid.quantity,
+        amount_paid: bid.amount_paid,
+        delivery: bid.delivery,
+        payment_asset: bid.payment_asset,
+        recipient: bid.recipient,
+    })
+}
+
+/// Recompute an auction's bid digest from its accepted bids in acceptance order
+/// (e.g. an indexer's bid_history), to audit the digest published in AuctionSettled
+/// Skip the event's digest_start leading bids first: the digest does not cover them.
+///
+/// # Returns
+/// None if there were no bids
+pub fn bid_log_digest<'a>(bids: impl IntoIterator<Item = &'a BidRecord>) -> Option<CryptoHash> {
+    bids.into_iter()
+        .fold(None, |digest, bid| Some(chain_bid_digest(digest, bid)))
+}
+
+/// Split `total` units pro rata to `weights`, rounding down
+/// Units left over by rounding go one each to the largest weights (earliest first on ties).
+pub fn pro_rata_units(total: u64, weights: &[Amount]) -> Vec<u64> {
+    let whole: u128 = weights.iter().map(|weight| u128::from(*weight)).sum();
+    if whole == 0 {
+        return vec![0; weights.len()];
+    }
+
+    let mut shares: Vec<u64> = weights
+        .iter()
+        .map(|weight| mul_div_floor(total as u128, u128::from(*weight), whole) as u64)
+        .collect();
+
+    let mut leftover = total - shares.iter().sum::<u64>();
+    let mut order: Vec<usize> = (0..weights.len()).collect();
+    order.sort_by(|a, b| weights[*b].cmp(&weights[*a]));
+    for index in order {
+        if leftover == 0 {
+            break;
+        }
+        shares[index] += 1;
+        leftover -= 1;
+    }
+
+    shares
+}
+
+/// total × part / whole, rounding down (part must not exceed whole)
+pub fn pro_rata_amount(total: Amount, part: Amount, whole: Amount) -> Amount {
+    if whole == Amount::ZERO {
+        return Amount::ZERO;
+    }
+    Amount::from_attos(mul_div_floor(
+        u128::from(total),
+        u128::from(part),
+        u128::from(whole),
+    ))
+}
+
+/// a × b / d with a 256-bit intermediate product (the quotient must fit in a u128)
+fn mul_div_floor(a: u128, b: u128, d: u128) -> u128 {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+
+    let low_low = a_lo * b_lo;
+    let low_high = a_lo * b_hi;
+    let high_low = a_hi * b_lo;
+    let middle = (low_low >> 64) + (low_high & MASK) + (high_low & MASK);
+    let lo = (low_low & MASK) | (middle << 64);
+    let hi = a_hi * b_hi + (low_high >> 64) + (high_low >> 64) + (middle >> 64);
+
+    // Binary long division of (hi, lo) by d
+    let mut remainder: u128 = 0;
+    let mut quotient: u128 = 0;
+    for bit_index in (0..256).rev() {
+        let bit = if bit_index >= 128 {
+            (hi >> (bit_index - 128)) & 1
+        } else {
+            (lo >> bit_index) & 1
+        };
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | bit;
+        if carry == 1 || remainder >= d {
+            remainder = remainder.wrapping_sub(d);
+            if bit_index < 128 {
+                quotient |= 1 << bit_index;
+            }
+        }
+    }
+
+    quotient
+}
+
+/// amount × rate in fixed point, split into whole and fractional parts to avoid overflow
+fn convert_with_rate(amount: Amount, rate: Amount, round_up: bool) -> Amount {
+    let amount = u128::from(amount);
+    let rate = u128::from(rate);
+    let (amount_whole, amount_frac) = (amount / ATTOS_PER_TOKEN, amount % ATTOS_PER_TOKEN);
+    let (rate_whole, rate_frac) = (rate / ATTOS_PER_TOKEN, rate % ATTOS_PER_TOKEN);
+
+    // Both fractions are below 10^18, so their product fits in a u128
+    let frac_product = amount_frac * rate_frac;
+    let mut attos = amount
+        .saturating_mul(rate_whole)
+        .saturating_add(amount_whole.saturating_mul(rate_frac))
+        .saturating_add(frac_product / ATTOS_PER_TOKEN);
+
+    if round_up && frac_product % ATTOS_PER_TOKEN != 0 {
+        attos = attos.saturating_add(1);
+    }
+
+    Amount::from_attos(attos)
+}
+
+/// Apply decay for `elapsed` clock ticks (microseconds or blocks)
+fn price_after_elapsed(
+    start_price: Amount,
+    floor_price: Amount,
+    price_decay_amount: Amount,
+    price_decay_interval: u64,
+    elapsed: u64,
+) -> Amount {
     // Calculate number of intervals that have passed
-    let intervals_passed = elapsed_micros / price_decay_interval;
+    let intervals_passed = elapsed / price_decay_interval;
 
     // Calculate total decrement
     let total_decay = price_decay_amount.saturating_mul(intervals_passed as u128);
@@ -45,7 +355,8 @@ pub fn calculate_current_price(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use linera_sdk::linera_base_types::TimeDelta;
+    use crate::types::{AcceptedAsset, PaymentAsset, PriceClock, DEFAULT_CLAIM_WINDOW};
+    use linera_sdk::linera_base_types::{AccountOwner, ApplicationId, CryptoHash, TimeDelta};
 
     #[test]
     fn test_price_at_start() {
@@ -126,4 +437,286 @@ mod tests {
         );
         assert_eq!(price, floor_price);
     }
+
+    #[test]
+    fn test_floor_reached_after() {
+        let decay_interval = 60_000_000;
+
+        // 90 tokens to drop at 1 token per interval
+        let ticks = floor_reached_after(
+            Amount::from_tokens(100),
+            Amount::from_tokens(10),
+            Amount::from_tokens(1),
+            decay_interval,
+        );
+        assert_eq!(ticks, Some(90 * decay_interval));
+
+        // Partial last step still needs a full interval
+        let ticks = floor_reached_after(
+            Amount::from_tokens(100),
+            Amount::from_tokens(10),
+            Amount::from_tokens(4),
+            decay_interval,
+        );
+        assert_eq!(ticks, Some(23 * decay_interval));
+
+        // No decay configured
+        let ticks = floor_reached_after(
+            Amount::from_tokens(100),
+            Amount::from_tokens(10),
+            Amount::ZERO,
+            decay_interval,
+        );
+        assert_eq!(ticks, None);
+    }
+
+    #[test]
+    fn test_price_by_block_height() {
+        let start_price = Amount::from_tokens(100);
+        let floor_price = Amount::from_tokens(10);
+        let decay_amount = Amount::from_tokens(1);
+        let decay_interval = 10; // Every 10 blocks
+        let start_height = BlockHeight(50);
+
+        let before_start = calculate_price_at_height(
+            start_price,
+            floor_price,
+            decay_amount,
+            decay_interval,
+            start_height,
+            BlockHeight(40),
+        );
+        assert_eq!(before_start, start_price);
+
+        let after_25_blocks = calculate_price_at_height(
+            start_price,
+            floor_price,
+            decay_amount,
+            decay_interval,
+            start_height,
+            BlockHeight(75),
+        );
+        assert_eq!(after_25_blocks, Amount::from_tokens(98));
+
+        let long_after = calculate_price_at_height(
+            start_price,
+            floor_price,
+            decay_amount,
+            decay_interval,
+            start_height,
+            BlockHeight(5_000),
+        );
+        assert_eq!(long_after, floor_price);
+    }
+
+    #[test]
+    fn test_convert_to_asset() {
+        let ten = Amount::from_tokens(10);
+
+        // 1:1 is the identity
+        assert_eq!(convert_to_asset(ten, Amount::ONE), ten);
+        assert_eq!(convert_to_asset_floor(ten, Amount::ONE), ten);
+
+        // 1.5 asset units per unit of account
+        let rate = Amount::from_attos(1_500_000_000_000_000_000);
+        assert_eq!(convert_to_asset(ten, rate), Amount::from_tokens(15));
+
+        // Fractional results round up for costs and down for proceeds
+        let half = Amount::from_attos(500_000_000_000_000_000);
+        let one_atto = Amount::from_attos(1);
+        assert_eq!(convert_to_asset(one_atto, half), one_atto);
+        assert_eq!(convert_to_asset_floor(one_atto, half), Amount::ZERO);
+
+        // Large amounts and rates do not overflow
+        let large = Amount::from_tokens(1_000_000_000);
+        let rate = Amount::from_attos(2_500_000_000_000_000_000);
+        assert_eq!(convert_to_asset(large, rate), Amount::from_tokens(2_500_000_000));
+    }
+
+    #[test]
+    fn test_asset_settlements() {
+        let token = PaymentAsset::Fungible(ApplicationId::new(CryptoHash::from([1u64; 4])));
+        let params = AuctionParams {
+            item_name: "Test".to_string(),
+            total_supply: 100,
+            start_price: Amount::from_tokens(10),
+            floor_price: Amount::from_tokens(1),
+            price_decay_interval: 1_000_000,
+            price_decay_amount: Amount::ONE,
+            start_time: Timestamp::from(0),
+            end_time: Timestamp::from(10_000_000),
+            creator: AccountOwner::CHAIN,
+            payment_asset: PaymentAsset::Native,
+            accepted_assets: vec![AcceptedAsset {
+                asset: token,
+                rate: Amount::from_tokens(2),
+            }],
+            fair_ordering_window: None,
+            price_clock: PriceClock::WallClock,
+            end_conditions: Vec::new(),
+            auto_distribute: false,
+            claim_window: DEFAULT_CLAIM_WINDOW,
+        };
+        let paid = [
+            AssetPaid {
+                payment_asset: PaymentAsset::Native,
+                quantity: 2,
+                amount_paid: Amount::from_tokens(20),
+            },
+            AssetPaid {
+                payment_asset: token,
+                quantity: 1,
+                amount_paid: Amount::from_tokens(16),
+            },
+            AssetPaid {
+                payment_asset: PaymentAsset::Native,
+                quantity: 1,
+                amount_paid: Amount::from_tokens(7),
+            },
+        ];
+
+        // Clearing at 5: native pays 3 × 5, the token pays 1 × 5 at 2 token units each
+        let settlements = asset_settlements(&params, Amount::from_tokens(5), &paid);
+        assert_eq!(settlements.len(), 2);
+        assert_eq!(settlements[0].payment_asset, PaymentAsset::Native);
+        assert_eq!(settlements[0].quantity, 3);
+        assert_eq!(settlements[0].cost, Amount::from_tokens(15));
+        assert_eq!(settlements[0].refund, Amount::from_tokens(12));
+        assert_eq!(settlements[1].cost, Amount::from_tokens(10));
+        assert_eq!(settlements[1].refund, Amount::from_tokens(6));
+
+        assert!(asset_settlements(&params, Amount::ONE, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_merge_settlement() {
+        let token = PaymentAsset::Fungible(ApplicationId::new(CryptoHash::from([1u64; 4])));
+        let native = AssetSettlement {
+            payment_asset: PaymentAsset::Native,
+            quantity: 2,
+            paid: Amount::from_tokens(20),
+            cost: Amount::from_tokens(10),
+            refund: Amount::from_tokens(10),
+        };
+        let mut settlement = SettlementResult {
+            allocated_quantity: 2,
+            clearing_price: Amount::from_tokens(5),
+            total_cost: Amount::from_tokens(10),
+            payments: vec![native],
+            gifted_quantity: 0,
+            gifted_from: None,
+        };
+        let other = SettlementResult {
+            allocated_quantity: 1,
+            clearing_price: Amount::from_tokens(5),
+            total_cost: Amount::from_tokens(10),
+            payments: vec![
+                AssetSettlement {
+                    quantity: 1,
+                    paid: Amount::from_tokens(6),
+                    cost: Amount::from_tokens(5),
+                    refund: Amount::ONE,
+                    ..native
+                },
+                AssetSettlement {
+                    payment_asset: token,
+                    quantity: 1,
+                    paid: Amount::from_tokens(10),
+                    cost: Amount::from_tokens(10),
+                    refund: Amount::ZERO,
+                },
+            ],
+            gifted_quantity: 1,
+            gifted_from: None,
+        };
+
+        merge_settlement(&mut settlement, other);
+        assert_eq!(settlement.allocated_quantity, 3);
+        assert_eq!(settlement.gifted_quantity, 1);
+        assert_eq!(settlement.total_cost, Amount::from_tokens(20));
+        assert_eq!(settlement.payments.len(), 2);
+        assert_eq!(settlement.payments[0].quantity, 3);
+        assert_eq!(settlement.payments[0].refund, Amount::from_tokens(11));
+        assert_eq!(settlement.payments[1].payment_asset, token);
+    }
+
+    #[test]
+    fn test_bid_log_digest() {
+        let chain_id = ChainId(CryptoHash::from([2u64; 4]));
+        let first = BidRecord {
+            bid_id: 0,
+            auction_id: 1,
+            user_chain: chain_id,
+            bidder: AccountOwner::CHAIN,
+            quantity: 2,
+            amount_paid: Amount::from_tokens(20),
+            timestamp: Timestamp::from(1_000),
+            claimed: false,
+            delivery: SettlementDelivery::UserChain,
+            payment_asset: PaymentAsset::Native,
+            recipient: None,
+        };
+        let second = BidRecord {
+            bid_id: 1,
+            quantity: 1,
+            amount_paid: Amount::from_tokens(9),
+            ..first.clone()
+        };
+
+        assert_eq!(bid_log_digest(&[]), None);
+        let digest = bid_log_digest(&[first.clone(), second.clone()]);
+        assert_eq!(
+            digest,
+            Some(chain_bid_digest(Some(chain_bid_digest(None, &first)), &second))
+        );
+
+        // Order matters, but the indexer's own timestamp and claim flag do not
+        assert_ne!(bid_log_digest(&[second.clone(), first.clone()]), digest);
+        let indexed = BidRecord {
+            timestamp: Timestamp::from(5_000),
+            claimed: true,
+            ..second.clone()
+        };
+        assert_eq!(bid_log_digest(&[first.clone(), indexed]), digest);
+
+        // Any change to a bid's terms breaks the chain
+        let tampered = BidRecord {
+            quantity: 3,
+            ..second
+        };
+        assert_ne!(bid_log_digest(&[first, tampered]), digest);
+    }
+
+    #[test]
+    fn test_pro_rata_units() {
+        let weights = [
+            Amount::from_tokens(50),
+            Amount::from_tokens(30),
+            Amount::from_tokens(20),
+        ];
+        assert_eq!(pro_rata_units(10, &weights), vec![5, 3, 2]);
+
+        // 7 units: floors are 3, 2, 1 - the leftover unit goes to the largest contributor
+        assert_eq!(pro_rata_units(7, &weights), vec![4, 2, 1]);
+
+        // Every unit is allocated
+        let uneven = [Amount::from_tokens(1), Amount::from_tokens(1), Amount::from_tokens(1)];
+        assert_eq!(pro_rata_units(100, &uneven).iter().sum::<u64>(), 100);
+
+        assert_eq!(pro_rata_units(5, &[Amount::ZERO]), vec![0]);
+    }
+
+    #[test]
+    fn test_pro_rata_amount() {
+        let total = Amount::from_tokens(90);
+        let share = pro_rata_amount(total, Amount::from_tokens(1), Amount::from_tokens(3));
+        assert_eq!(share, Amount::from_tokens(30));
+
+        // Large values do not overflow the intermediate product
+        let large = Amount::from_tokens(1_000_000_000_000);
+        let share = pro_rata_amount(large, large, large.saturating_mul(2));
+        assert_eq!(share, Amount::from_tokens(500_000_000_000));
+
+        assert_eq!(pro_rata_amount(total, total, Amount::ZERO), Amount::ZERO);
+    }
 }